@@ -0,0 +1,181 @@
+// Compares the strategies, and std's ilog10, for every width over a few
+// distributions of input:
+//
+//   uniform      every bit pattern equally likely, so nearly all x are huge
+//   log-uniform  every bit length equally likely
+//   small        mostly small numbers, as when counting digits of typical data
+//   boundary     10^k - 1 and 10^k for every k, where a wrong guess would show
+//
+// For each one it reports throughput, with independent calls that can overlap,
+// and latency, with each call's input depending on the last call's result.
+//
+//   cargo bench --bench strategies
+//
+// ILOG10_BENCH_MS sets roughly how long each measurement runs (default 20).
+
+use ilog10::strategy::{BinarySearch, CarryTable, Linear, Strategy, TwoTable};
+use ilog10::IntLog10;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const INPUTS: usize = 4096;
+
+// xorshift64*, which is plenty random for picking inputs.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    fn next_u128(&mut self) -> u128 {
+        ((self.next() as u128) << 64) | self.next() as u128
+    }
+
+    fn below(&mut self, n: u32) -> u32 {
+        (self.next() % n as u64) as u32
+    }
+}
+
+// The inputs, as u128s that fit in the width; zero becomes one.
+fn inputs(distribution: &str, bits: u32, rng: &mut Rng) -> Vec<u128> {
+    let max = u128::MAX >> (128 - bits);
+    let mut inputs: Vec<u128> = match distribution {
+        "uniform" => (0..INPUTS).map(|_| rng.next_u128() & max).collect(),
+        "log-uniform" => (0..INPUTS)
+            .map(|_| {
+                let len = rng.below(bits) + 1;
+                let low = 1u128 << (len - 1);
+                low | (rng.next_u128() & (low - 1))
+            })
+            .collect(),
+        "small" => (0..INPUTS)
+            .map(|_| {
+                // Each extra digit is half as likely as the one before.
+                let mut digits = 1;
+                while digits < 39 && rng.below(2) == 0 {
+                    digits += 1;
+                }
+                let limit = 10u128.saturating_pow(digits).min(max);
+                rng.next_u128() % limit
+            })
+            .collect(),
+        "boundary" => {
+            let mut edges = Vec::new();
+            let mut pow = 10u128;
+            while pow <= max {
+                edges.push(pow - 1);
+                edges.push(pow);
+                pow = match pow.checked_mul(10) {
+                    Some(next) => next,
+                    None => break,
+                };
+            }
+            (0..INPUTS).map(|i| edges[i % edges.len()]).collect()
+        }
+        _ => unreachable!(),
+    };
+    for x in &mut inputs {
+        *x = (*x).max(1);
+    }
+    inputs
+}
+
+// Calls f on every input, over and over, for about `time`, and returns the
+// nanoseconds per call.
+fn measure<T: Copy>(inputs: &[T], time: Duration, mut run: impl FnMut(&[T]) -> u32) -> f64 {
+    black_box(run(inputs)); // warm up
+    let start = Instant::now();
+    let mut calls = 0u64;
+    while start.elapsed() < time {
+        black_box(run(inputs));
+        calls += inputs.len() as u64;
+    }
+    start.elapsed().as_nanos() as f64 / calls as f64
+}
+
+// Throughput: the calls are independent, so they can overlap.
+fn throughput<T: Copy>(inputs: &[T], time: Duration, f: impl Fn(T) -> u32) -> f64 {
+    measure(inputs, time, |inputs| {
+        inputs.iter().map(|&x| f(black_box(x))).fold(0, u32::wrapping_add)
+    })
+}
+
+// Latency: each input is mixed with the previous result, ANDed with a mask
+// the compiler can't see is zero, so each call has to wait for the last one.
+fn latency<T, F>(inputs: &[T], time: Duration, f: F) -> f64
+where
+    T: Copy + std::ops::BitOr<Output = T> + From<u8>,
+    F: Fn(T) -> u32,
+{
+    let mask = black_box(0u32);
+    measure(inputs, time, |inputs| {
+        let mut last = 0;
+        for &x in inputs {
+            last = f(x | T::from((last & mask) as u8));
+        }
+        last
+    })
+}
+
+// Both come in as nanoseconds per call; throughput goes out as millions of calls
+// per second.
+fn report(name: &str, throughput: f64, latency: f64) {
+    println!(
+        "    {:<14} {:>8.1} M/s {:>8.2} ns",
+        name,
+        1e3 / throughput,
+        latency
+    );
+}
+
+fn bench_strategy<S: Strategy, T>(name: &str, inputs: &[T], time: Duration)
+where
+    T: IntLog10 + Copy + std::ops::BitOr<Output = T> + From<u8>,
+{
+    report(
+        name,
+        throughput(inputs, time, |x| x.log10_floor_with::<S>()),
+        latency(inputs, time, |x| x.log10_floor_with::<S>()),
+    );
+}
+
+macro_rules! bench_width {
+    ($t:ty, $rng:expr, $time:expr) => {
+        for distribution in ["uniform", "log-uniform", "small", "boundary"] {
+            let inputs: Vec<$t> = inputs(distribution, <$t>::BITS, $rng)
+                .into_iter()
+                .map(|x| x as $t)
+                .collect();
+            println!("{} {}", stringify!($t), distribution);
+            bench_strategy::<TwoTable, $t>("two-table", &inputs, $time);
+            bench_strategy::<CarryTable, $t>("carry-table", &inputs, $time);
+            bench_strategy::<BinarySearch, $t>("binary-search", &inputs, $time);
+            bench_strategy::<Linear, $t>("linear", &inputs, $time);
+            report(
+                "std ilog10",
+                throughput(&inputs, $time, |x: $t| x.ilog10()),
+                latency(&inputs, $time, |x: $t| x.ilog10()),
+            );
+        }
+    };
+}
+
+fn main() {
+    let ms = std::env::var("ILOG10_BENCH_MS")
+        .ok()
+        .and_then(|ms| ms.parse().ok())
+        .unwrap_or(20);
+    let time = Duration::from_millis(ms);
+    let rng = &mut Rng(0x9E37_79B9_7F4A_7C15);
+
+    println!("                   throughput   latency");
+    bench_width!(u8, rng, time);
+    bench_width!(u16, rng, time);
+    bench_width!(u32, rng, time);
+    bench_width!(u64, rng, time);
+    bench_width!(u128, rng, time);
+}
@@ -1,87 +1,1331 @@
-#![feature(int_log)]
+#![no_std]
+// Without the unsafe-fast feature there's no unsafe code here; the only
+// exception is the *_unchecked functions, which are declared unsafe and call
+// each other as such, but which all come down to safe code.  See lookup!.
+#![cfg_attr(not(feature = "unsafe-fast"), deny(unsafe_code))]
+// The safety requirements are in plain comments, like everything else here.
+#![allow(clippy::missing_safety_doc)]
+#![cfg_attr(all(test, feature = "nightly"), feature(test))]
+
+#[macro_use]
+mod tables;
+pub mod reference;
+pub mod strategy;
+
+use core::fmt;
+use core::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+    NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
+};
+use strategy::Strategy;
+use tables::{
+    div_magics_ok, float_limits_ok, guesses_ok, inv5s_ok, limits_from_pows, limits_len, limits_ok,
+    log10_pow2_ok, log10_pow5_ok, log2_pow10_ok, logs_len, make_carries, make_div_magics,
+    make_div_shifts, make_float_limits, make_guesses, make_inv5s, make_limits, make_mids, make_pows,
+    make_quotients, mids_ok, mul_wide, pows_ok, quotients_ok,
+};
 
 // This is a proof of concept for doing integer log10 based on log2.
-// This version returns the floor, but it could be trivially modified
-// to round or produce the ceiling as desired.  It could also be used
-// with other bases > 2.
+// It produces the floor, and with one more table lookup the ceiling or the
+// rounded value.  The same technique works for other bases > 2 too;
+// see ilog_floor.  The alternatives are in strategy, and
+// `cargo bench --bench strategies` compares them all.
 
 // Index into this with floor(log2(x)) to get a guess at floor(log10(x)).
 // The value might be one lower than it should be, so then you
 // have to check whether x > LIMITS(guess).
-// In reality this would have 128 u8 entries in order to support u128.
-// And you would generate those entries with a macro, but for this demo
-// it's probably clearer to just write the entries out like this.
-const LOG10S_FOR_LOG2S: [u8; 16] = [
-//        log2
-//        ---- ------
-    0, //   0      1
-    0, //   1      2
-    0, //   2      4
-    0, //   3      8  *
-    1, //   4     16
-    1, //   5     32
-    1, //   6     64  *
-    2, //   7    128
-    2, //   8    256
-    2, //   9    512  *
-    3, //  10   1024
-    3, //  11   2048
-    3, //  12   4096
-    3, //  13   8192  *
-    4, //  14  16384
-    4, //  15  32768
-];
-
-// LIMITS[log] is the highest x for which floor(log10(x)) == log.
-// In reality you would need this to have all such numbers that fit in a u128.
-// And you might want to have a separate version for types up to u32, so that
-// you don't have to manipulate u128s.
-const LIMITS: [u16; 5] = [
-    9,  // maximum x for which floor(log10(x)) is 0
-    99,  // maximum x for which floor(log10(x)) is 1
-    999,  // ...
-    9_999,
-    u16::MAX // can't use 99_999 because it's not u16
-];
+// There are 128 entries so that every width up to u128 can share the table;
+// narrower types just never look past their own bit width.
+// It's generated (and checked) at compile time, and starts out
+//
+//   log2   2^log2   guess
+//   ----   ------   -----
+//      0        1       0
+//      1        2       0
+//      2        4       0
+//      3        8       0  *
+//      4       16       1
+//    ...      ...     ...
+//    127   1.7e38      38
+//
+// where the * marks a range of x that crosses a power of ten, so that
+// the guess might be one too low.
+const LOG10S_FOR_LOG2S: [u8; 128] = make_guesses(10);
+const _: () = assert!(guesses_ok(10, &LOG10S_FOR_LOG2S));
 
-// Returns the floor of log base 10 of its argument.
-// In reality you would make this generic, supporting types up to u128.
-// The same tables could be used for all of the u* types.
-// This routine uses the floor(log2(x)) function in order to get good performance;
-// on modern architectures there is typically a fairly quick instruction for that.
-pub fn log10_floor(x: u16) -> u8 {
-    let log2x = x.log2() as usize;
-    let log10x_guess = unsafe {
-        // SAFETY: ilog2_floor of a u16 can only be 0..15,
-        // for which there are elements in the array.
-        *(&LOG10S_FOR_LOG2S).get_unchecked(log2x)
+// Every table is indexed with lookup!.  When the table's length is a power of
+// two, as for LOG10S_FOR_LOG2S and the LIMITS and MIDS tables, it masks the
+// index with the length minus one, which keeps the index in bounds where the
+// compiler can see it, and the bounds check goes away.  Tables with one entry
+// per power, like POW10_Ux and the division tables, and the float limits, which
+// would be mostly padding as powers of two, have other lengths, and for those
+// it clamps the index to the last entry instead.  The indices are always in
+// bounds anyway, so neither ever changes anything, and the length is a
+// constant, so only one of the two is ever compiled in.
+// With the unsafe-fast feature it skips even that and reads the entry
+// unchecked instead.
+// The one exception is the carry tables of log10_u32, log10_u64 and
+// log10_u128: those are safe functions whose index is out of bounds for 0, so
+// they index plainly and panic rather than read past the end.
+#[cfg(not(feature = "unsafe-fast"))]
+macro_rules! lookup {
+    ($table:expr, $index:expr) => {{
+        let index = $index as usize;
+        if $table.len().is_power_of_two() {
+            $table[index & ($table.len() - 1)]
+        } else if index < $table.len() {
+            $table[index]
+        } else {
+            $table[$table.len() - 1]
+        }
+    }};
+}
+
+#[cfg(feature = "unsafe-fast")]
+macro_rules! lookup {
+    ($table:expr, $index:expr) => {
+        // SAFETY: Every lookup is in bounds; see the comment above each one.
+        unsafe { *$table.as_ptr().add($index as usize) }
+    };
+}
+
+// POW10_Ux[k] is 10^k, for every k for which that fits in the type; for
+// example POW10_U16 is [1, 10, 100, 1_000, 10_000].  These are public, and the
+// LIMITS are built from them, so there's just the one definition of the powers.
+//
+// LIMITS_Ux[log] is the highest x for which floor(log10(x)) == log.
+// There is a separate table per width so that the comparison is done in the
+// type itself rather than in u128.  Each table has an entry for every guess
+// LOG10S_FOR_LOG2S can produce for that width; when the true limit doesn't fit
+// in the type, the last entry is the type's MAX instead, so the guess stands.
+// For example LIMITS_U16 is [9, 99, 999, 9_999, u16::MAX], padded out with
+// more u16::MAX to a power of two, for lookup!.
+//
+// MIDS_Ux[log] is the highest x that rounds down to log, i.e. the highest x
+// below the geometric midpoint 10^(log + 0.5).  Since x < 10^(log + 0.5) exactly
+// when x^2 < 10^(2*log + 1), that's floor(sqrt(10^(2*log + 1))), which is never
+// itself a midpoint because 10^odd isn't a perfect square.
+// There is an entry for every floor(log10(x)) the width can have, again padded
+// with MAX to a power of two.
+//
+// DIV_MAGICS_Ux[k] and DIV_SHIFTS_Ux[k] divide by 10^k with a multiply and two
+// shifts instead of a division, as make_div_magics describes.  There is an entry
+// for every k that POW10_Ux has, though entry 0 is never used.
+//
+// INV5S_Ux[k] is the inverse of 5^k mod 2^BITS, and QUOTIENTS_Ux[k] is
+// MAX / 10^k; together they test whether 10^k divides x, as make_inv5s
+// describes.  Again there is an entry for every k that POW10_Ux has.
+macro_rules! log10_tables {
+    (
+        $t:ty, $pow10:ident, $limits:ident, $mids:ident, $magics:ident, $shifts:ident,
+        $inv5s:ident, $quotients:ident
+    ) => {
+        pub const $pow10: [$t; logs_len(10, <$t>::MAX as u128)] = {
+            const WIDE: [u128; logs_len(10, <$t>::MAX as u128)] = make_pows(10);
+            assert!(pows_ok(10, &WIDE, <$t>::MAX as u128));
+            narrow!($t, WIDE)
+        };
+
+        const $limits: [$t; limits_len(&LOG10S_FOR_LOG2S, <$t>::BITS).next_power_of_two()] = {
+            const POWS: [u128; $pow10.len()] = widen!($pow10);
+            const WIDE: [u128; limits_len(&LOG10S_FOR_LOG2S, <$t>::BITS).next_power_of_two()] =
+                limits_from_pows(&POWS, <$t>::MAX as u128);
+            assert!(limits_ok(10, <$t>::BITS, &LOG10S_FOR_LOG2S, &WIDE, <$t>::MAX as u128));
+            narrow!($t, WIDE)
+        };
+
+        const $mids: [$t; logs_len(10, <$t>::MAX as u128).next_power_of_two()] = {
+            const WIDE: [u128; logs_len(10, <$t>::MAX as u128).next_power_of_two()] =
+                make_mids(10, <$t>::MAX as u128);
+            assert!(mids_ok(10, &WIDE, <$t>::MAX as u128));
+            narrow!($t, WIDE)
+        };
+
+        const $shifts: [u8; $pow10.len()] = make_div_shifts(<$t>::BITS);
+
+        const $magics: [$t; $pow10.len()] = {
+            const WIDE: [u128; $pow10.len()] = make_div_magics(<$t>::BITS);
+            assert!(div_magics_ok(<$t>::BITS, &WIDE, &$shifts));
+            narrow!($t, WIDE)
+        };
+
+        const $inv5s: [$t; $pow10.len()] = {
+            const WIDE: [u128; $pow10.len()] = make_inv5s(<$t>::BITS);
+            assert!(inv5s_ok(<$t>::BITS, &WIDE));
+            narrow!($t, WIDE)
+        };
+
+        const $quotients: [$t; $pow10.len()] = {
+            const POWS: [u128; $pow10.len()] = widen!($pow10);
+            const WIDE: [u128; $pow10.len()] = make_quotients(&POWS, <$t>::MAX as u128);
+            assert!(quotients_ok(&POWS, &WIDE, <$t>::MAX as u128));
+            narrow!($t, WIDE)
+        };
+    };
+}
+
+log10_tables!(
+    u8, POW10_U8, LIMITS_U8, MIDS_U8, DIV_MAGICS_U8, DIV_SHIFTS_U8,
+    INV5S_U8, QUOTIENTS_U8
+);
+log10_tables!(
+    u16, POW10_U16, LIMITS_U16, MIDS_U16, DIV_MAGICS_U16, DIV_SHIFTS_U16,
+    INV5S_U16, QUOTIENTS_U16
+);
+log10_tables!(
+    u32, POW10_U32, LIMITS_U32, MIDS_U32, DIV_MAGICS_U32, DIV_SHIFTS_U32,
+    INV5S_U32, QUOTIENTS_U32
+);
+log10_tables!(
+    u64, POW10_U64, LIMITS_U64, MIDS_U64, DIV_MAGICS_U64, DIV_SHIFTS_U64,
+    INV5S_U64, QUOTIENTS_U64
+);
+log10_tables!(
+    u128, POW10_U128, LIMITS_U128, MIDS_U128, DIV_MAGICS_U128, DIV_SHIFTS_U128,
+    INV5S_U128, QUOTIENTS_U128
+);
+
+// Integer log10 for the primitive integer types.
+// The same LOG10S_FOR_LOG2S table is used for all of them; only the LIMITS
+// table (and the type the comparison is done in) differs.
+// The signed types work on the magnitude, so (-1000).log10_floor() is 3, and
+// i128::MIN works even though its absolute value isn't an i128.
+pub trait IntLog10: Sized {
+    // The highest log10_floor any value of the type has, and the most
+    // decimal_digits and display_len, e.g. 19, 20 and 20 for u64, and 18, 19
+    // and 20 for i64.  They're consts so they can size buffers:
+    // [u8; <u64 as IntLog10>::MAX_DIGITS as usize].
+    const MAX_LOG10: u32;
+    const MAX_DIGITS: u32;
+    const MAX_DISPLAY_LEN: u32;
+
+    // Returns the floor of log base 10 of the magnitude of self.
+    // Panics if self is 0, since there is no sensible answer for that.
+    fn log10_floor(self) -> u32;
+
+    // Returns the ceiling of log base 10 of the magnitude of self.
+    // Panics if self is 0.
+    fn log10_ceil(self) -> u32;
+
+    // Returns log base 10 of the magnitude of self rounded to the nearest
+    // integer, where "nearest" is on the log scale: x rounds up to k + 1 once it
+    // reaches 10^(k + 0.5), about 3.16 * 10^k.  Panics if self is 0.
+    fn log10_round(self) -> u32;
+
+    // These are the same, but return None for 0 instead of panicking.
+    fn checked_log10_floor(self) -> Option<u32>;
+    fn checked_log10_ceil(self) -> Option<u32>;
+    fn checked_log10_round(self) -> Option<u32>;
+
+    // These are for callers who want the logarithm in the mathematical sense:
+    // they fail with Log10Error::Zero for 0, and, unlike everything above,
+    // with Log10Error::Negative for negative values rather than using the magnitude.
+    fn try_log10_floor(self) -> Result<u32, Log10Error>;
+    fn try_log10_ceil(self) -> Result<u32, Log10Error>;
+    fn try_log10_round(self) -> Result<u32, Log10Error>;
+
+    // Returns the floor of log base B of the magnitude of self.
+    // B must be at least 2; anything less is a compile-time error.
+    // Panics if self is 0.
+    fn ilog_floor<const B: u128>(self) -> u32;
+
+    // The same as log10_floor, but with no zero check at all.
+    // Safety: self must not be 0.  Builds with debug assertions check that
+    // and panic; otherwise 0 gives a meaningless answer, and with the
+    // unsafe-fast feature it's undefined behavior.
+    #[allow(unsafe_code)]
+    unsafe fn log10_floor_unchecked(self) -> u32;
+
+    // The same as log10_floor, but computed with the given strategy, e.g.
+    // x.log10_floor_with::<strategy::Linear>().  Panics if self is 0.
+    fn log10_floor_with<S: Strategy>(self) -> u32;
+
+    // Returns log10_floor along with 10^log10_floor, e.g. (3, 1_000) for 1_234,
+    // for splitting x without working out the power again.  For signed types
+    // it's still the magnitude whose floor is taken, and the power has the
+    // sign of self, e.g. (3, -1_000) for -1_234.  Panics if self is 0.
+    fn log10_floor_with_pow(self) -> (u32, Self);
+
+    // Returns log10_floor along with what's left of self once that power is
+    // taken off, e.g. (3, 234) for 1_234 and (3, -234) for -1_234, so that the
+    // power and the rest add back up to self.  Panics if self is 0.
+    fn log10_floor_rem(self) -> (u32, Self);
+
+    // Returns how many decimal digits the magnitude of self has, counting 0 as
+    // one digit, i.e. log10_floor + 1 for everything but 0.  Never panics.
+    fn decimal_digits(self) -> u32;
+
+    // Returns how many characters self prints as with Display: decimal_digits,
+    // plus one for the minus sign if self is negative.
+    fn display_len(self) -> u32;
+
+    // The base-10 trailing_zeros: how many zeros self ends with in decimal,
+    // e.g. 2 for 1_200.  0 is written as just "0", so it has none.
+    fn decimal_trailing_zeros(self) -> u32;
+
+    // Returns self with its decimal trailing zeros taken off, and how many
+    // there were, so that self == mantissa * 10^exponent; e.g. (12, 2) for
+    // 1_200, and (-12, 2) for -1_200.  0 gives (0, 0).
+    fn strip_decimal_zeros(self) -> (Self, u32);
+}
+
+// Why the try_log10_* functions failed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Log10Error {
+    Zero,
+    Negative,
+}
+
+impl fmt::Display for Log10Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Log10Error::Zero => f.write_str("logarithm of zero"),
+            Log10Error::Negative => f.write_str("logarithm of a negative number"),
+        }
+    }
+}
+
+impl core::error::Error for Log10Error {}
+
+// Integer log10 for the NonZero types.
+// Zero is the only input the other functions can fail on, so this can't fail,
+// and there is no zero check (and no panic) anywhere on the path.
+pub trait NonZeroLog10 {
+    // Returns the floor of log base 10 of the magnitude of self.
+    fn log10(self) -> u32;
+}
+
+// Powers of ten for the unsigned types, read from the POW10 tables, e.g.
+// for_u64::checked_pow10(19) or checked_pow10::<u64>(19).
+pub trait Pow10: Copy {
+    // Returns 10^k, or None if that doesn't fit in Self.
+    fn checked_pow10(k: u32) -> Option<Self>;
+
+    // Returns 10^k, or MAX if that doesn't fit in Self.
+    fn saturating_pow10(k: u32) -> Self;
+
+    // Returns 10^k with no range check.
+    // Safety: k must be at most IntLog10::MAX_LOG10.  Builds with debug
+    // assertions check that and panic; otherwise a bigger k gives a
+    // meaningless answer, and with the unsafe-fast feature it's undefined
+    // behavior.
+    #[allow(unsafe_code)]
+    unsafe fn pow10_unchecked(k: u32) -> Self;
+
+    // Like is_power_of_two and next_power_of_two on the integer types.
+
+    // Returns true if self is 10^k for some k.  0 isn't.
+    fn is_power_of_ten(self) -> bool;
+
+    // Returns the smallest power of ten >= self, which is 1 for 0.
+    // Like next_power_of_two, if that doesn't fit it panics in builds with
+    // debug assertions and returns 0 otherwise.
+    fn next_power_of_ten(self) -> Self;
+
+    // The same, but None if the power doesn't fit.
+    fn checked_next_power_of_ten(self) -> Option<Self>;
+
+    // Returns the largest power of ten <= self.  Panics if self is 0.
+    fn prev_power_of_ten(self) -> Self;
+
+    // Returns self / 10^k, self % 10^k, or both, e.g. for cutting self down to
+    // its leading digits.  These multiply by a magic number from a table rather
+    // than dividing.  A k too big for 10^k to fit is fine: self / 10^k is then 0.
+    fn div_pow10(self, k: u32) -> Self;
+    fn rem_pow10(self, k: u32) -> Self;
+    fn divrem_pow10(self, k: u32) -> (Self, Self);
+}
+
+// Exact floor(log10) for f32 and f64, which x.log10().floor() isn't: the f64
+// nearest 1e23 is a little under 10^23, so the answer is 22, but its log10
+// rounds to 23.0.  Like the signed integers, these work on the magnitude.
+pub trait FloatLog10 {
+    // The lowest and highest log10_floor any finite value of the type has,
+    // e.g. -324 and 308 for f64, from the smallest subnormal and MAX.
+    const MIN_LOG10: i32;
+    const MAX_LOG10: i32;
+
+    // Returns the floor of log base 10 of the magnitude of self, which is the
+    // exponent self has in scientific notation.  Subnormals are fine.
+    // Panics if self is 0, infinite or NaN.
+    fn log10_floor(self) -> i32;
+
+    // The same, but None for 0, infinity and NaN instead of panicking.
+    fn checked_log10_floor(self) -> Option<i32>;
+}
+
+// The high half of the double-width product of two $u, for dividing with the
+// DIV_MAGICS tables.  u128 has nothing wider, so it uses mul_wide.
+macro_rules! mul_high {
+    (u8, $a:expr, $b:expr) => { mul_high!(u8, u16, $a, $b) };
+    (u16, $a:expr, $b:expr) => { mul_high!(u16, u32, $a, $b) };
+    (u32, $a:expr, $b:expr) => { mul_high!(u32, u64, $a, $b) };
+    (u64, $a:expr, $b:expr) => { mul_high!(u64, u128, $a, $b) };
+    (u128, $a:expr, $b:expr) => { mul_wide($a, $b).0 };
+    ($u:ident, $wide:ident, $a:expr, $b:expr) => {
+        (($a as $wide * $b as $wide) >> <$u>::BITS) as $u
+    };
+}
+
+// Each integer type gets a module, for_u64 and so on, holding const fn versions
+// of everything, so that something like for_u64::log10_floor(u64::MAX) can size
+// an array.  The trait methods just call these, since trait methods can't be
+// const.  The modules aren't just named after the types, like the old std::u32,
+// since then a glob import of this crate would shadow the types themselves.
+// $m is the module; $t is the type being implemented; $u is the type of the
+// tables used for it, which is only different for usize.
+// $with is the Strategy method for $u.
+macro_rules! impl_int_log10 {
+    (
+        $m:ident, $t:ident, $u:ident, $nz:ty, $pow10:ident, $limits:ident, $mids:ident,
+        $magics:ident, $shifts:ident, $inv5s:ident, $quotients:ident, $with:ident
+    ) => {
+        pub mod $m {
+            use super::*;
+
+            // The guess-and-correct step, given floor(log2(x)).
+            // That's BITS - 1 - leading_zeros, which is a quick instruction or
+            // two on most architectures; it's left to the caller so that
+            // log10_nonzero, which doesn't need a zero check first, can share this.
+            // x must not be 0, and log2x must really be floor(log2(x)).
+            #[inline(always)]
+            const fn log10_floor_from_log2(x: $t, log2x: u32) -> u32 {
+                let x = x as $u;
+                // log2 of x is less than the bit width of $u, which is at most
+                // 128, the length of the array.
+                let log10x_guess = lookup!(LOG10S_FOR_LOG2S, log2x);
+                // $limits has an entry for every guess LOG10S_FOR_LOG2S gives
+                // for a log2 below the width of $u.
+                let limit = lookup!($limits, log10x_guess);
+                if x > limit {
+                    log10x_guess as u32 + 1
+                } else {
+                    log10x_guess as u32
+                }
+            }
+
+            // The same, but also returning 10^floor(log10(x)).  When the guess
+            // is corrected, that's just one more than the limit it was compared
+            // with; otherwise it comes from $pow10.
+            #[inline(always)]
+            const fn log10_floor_with_pow_from_log2(x: $t, log2x: u32) -> (u32, $t) {
+                let x = x as $u;
+                // The same lookups as log10_floor_from_log2.
+                let log10x_guess = lookup!(LOG10S_FOR_LOG2S, log2x);
+                let limit = lookup!($limits, log10x_guess);
+                if x > limit {
+                    // x is above the limit, so it isn't MAX, and the limit is
+                    // 10^(guess + 1) - 1.
+                    (log10x_guess as u32 + 1, (limit + 1) as $t)
+                } else {
+                    // The guess is a real log of some x, so $pow10 has it.
+                    (log10x_guess as u32, lookup!($pow10, log10x_guess) as $t)
+                }
+            }
+
+            // Returns x / 10^k if 10^k divides x, with no division.  k must
+            // be at most MAX_LOG10.  When it does, multiplying by the inverse
+            // of 5^k leaves 2^k * (x / 10^k), and rotating that right by k
+            // leaves the quotient.  When it doesn't, either some of the low k
+            // bits of x were set, and the rotation puts them on top, or x isn't
+            // a multiple of 5^k, and the product is too big; either way the
+            // result is more than MAX / 10^k.
+            #[inline(always)]
+            const fn div_exact_pow10(x: $u, k: u32) -> Option<$u> {
+                // $inv5s and $quotients have an entry for every k up to
+                // MAX_LOG10.
+                let rotated = x.wrapping_mul(lookup!($inv5s, k)).rotate_right(k);
+                if rotated <= lookup!($quotients, k) {
+                    Some(rotated)
+                } else {
+                    None
+                }
+            }
+
+            // See IntLog10 for what these do.
+
+            // MAX is the first entry of $limits that isn't a power of ten
+            // minus one, and its index is the highest log.
+            pub const MAX_LOG10: u32 = log10_floor(<$t>::MAX);
+            pub const MAX_DIGITS: u32 = MAX_LOG10 + 1;
+            pub const MAX_DISPLAY_LEN: u32 = MAX_DIGITS;
+
+            #[inline]
+            pub const fn log10_floor(x: $t) -> u32 {
+                if x == 0 {
+                    panic!("log10_floor of 0 is undefined");
+                }
+                log10_floor_from_log2(x, <$t>::BITS - 1 - x.leading_zeros())
+            }
+
+            #[inline]
+            pub const fn log10_ceil(x: $t) -> u32 {
+                if x == 0 {
+                    panic!("log10_ceil of 0 is undefined");
+                }
+                // Only exact powers of ten have the same floor and ceiling,
+                // and x - 1 has a lower floor than x just for those.
+                if x == 1 {
+                    0
+                } else {
+                    log10_floor(x - 1) + 1
+                }
+            }
+
+            #[inline]
+            pub const fn log10_round(x: $t) -> u32 {
+                let log10x = log10_floor(x);
+                // $mids has an entry for every floor(log10(x)) of a $u.
+                let mid = lookup!($mids, log10x);
+                if x as $u > mid {
+                    log10x + 1
+                } else {
+                    log10x
+                }
+            }
+
+            #[inline]
+            pub const fn checked_log10_floor(x: $t) -> Option<u32> {
+                if x == 0 {
+                    None
+                } else {
+                    Some(log10_floor(x))
+                }
+            }
+
+            #[inline]
+            pub const fn checked_log10_ceil(x: $t) -> Option<u32> {
+                if x == 0 {
+                    None
+                } else {
+                    Some(log10_ceil(x))
+                }
+            }
+
+            #[inline]
+            pub const fn checked_log10_round(x: $t) -> Option<u32> {
+                if x == 0 {
+                    None
+                } else {
+                    Some(log10_round(x))
+                }
+            }
+
+            #[inline]
+            pub const fn try_log10_floor(x: $t) -> Result<u32, Log10Error> {
+                if x == 0 {
+                    Err(Log10Error::Zero)
+                } else {
+                    Ok(log10_floor(x))
+                }
+            }
+
+            #[inline]
+            pub const fn try_log10_ceil(x: $t) -> Result<u32, Log10Error> {
+                if x == 0 {
+                    Err(Log10Error::Zero)
+                } else {
+                    Ok(log10_ceil(x))
+                }
+            }
+
+            #[inline]
+            pub const fn try_log10_round(x: $t) -> Result<u32, Log10Error> {
+                if x == 0 {
+                    Err(Log10Error::Zero)
+                } else {
+                    Ok(log10_round(x))
+                }
+            }
+
+            #[inline]
+            pub const fn ilog_floor<const B: u128>(x: $t) -> u32 {
+                if x == 0 {
+                    panic!("ilog_floor of 0 is undefined");
+                }
+                ilog_floor_from_log2::<B>(x as u128, <$t>::BITS - 1 - x.leading_zeros())
+            }
+
+            // See NonZeroLog10.
+            #[inline]
+            pub const fn log10_nonzero(x: $nz) -> u32 {
+                log10_floor_from_log2(x.get(), <$t>::BITS - 1 - x.leading_zeros())
+            }
+
+            // Safety: x must not be 0.
+            // The body is safe code all the same: for 0, log2 wraps around to
+            // u32::MAX, and without unsafe-fast lookup! masks that back into
+            // the table, so the answer is garbage but nothing is out of bounds.
+            #[inline]
+            #[allow(unsafe_code)]
+            pub const unsafe fn log10_floor_unchecked(x: $t) -> u32 {
+                debug_assert!(x != 0, "log10_floor_unchecked of 0");
+                log10_floor_from_log2(x, (<$t>::BITS - 1).wrapping_sub(x.leading_zeros()))
+            }
+
+            #[inline]
+            pub const fn log10_floor_with_pow(x: $t) -> (u32, $t) {
+                if x == 0 {
+                    panic!("log10_floor of 0 is undefined");
+                }
+                log10_floor_with_pow_from_log2(x, <$t>::BITS - 1 - x.leading_zeros())
+            }
+
+            #[inline]
+            pub const fn log10_floor_rem(x: $t) -> (u32, $t) {
+                let (log, pow) = log10_floor_with_pow(x);
+                (log, x - pow)
+            }
+
+            #[inline]
+            pub const fn decimal_digits(x: $t) -> u32 {
+                // x | 1 has the same floor(log10) as x, unless x is 0, which it
+                // turns into 1, giving one digit without a separate zero check.
+                // (Setting the low bit only changes an even x, and x + 1 is only
+                // a power of ten when x is odd.)
+                let x = x | 1;
+                log10_floor_from_log2(x, <$t>::BITS - 1 - x.leading_zeros()) + 1
+            }
+
+            #[inline]
+            pub const fn display_len(x: $t) -> u32 {
+                decimal_digits(x)
+            }
+
+            #[inline]
+            pub const fn decimal_trailing_zeros(x: $t) -> u32 {
+                strip_decimal_zeros(x).1
+            }
+
+            #[inline]
+            pub const fn strip_decimal_zeros(x: $t) -> ($t, u32) {
+                if x == 0 {
+                    return (0, 0);
+                }
+                // Try taking off the biggest power of two zeros up to
+                // MAX_LOG10, then half as many, and so on down to one; every
+                // count up to MAX_LOG10 is a sum of some of those.
+                let mut x = x as $u;
+                let mut zeros = 0;
+                let mut step = 1 << MAX_LOG10.ilog2();
+                while step > 0 {
+                    if let Some(quotient) = div_exact_pow10(x, step) {
+                        x = quotient;
+                        zeros += step;
+                    }
+                    step /= 2;
+                }
+                (x as $t, zeros)
+            }
+
+            // See Pow10 for these.
+
+            #[inline]
+            pub const fn checked_pow10(k: u32) -> Option<$t> {
+                if (k as usize) < $pow10.len() {
+                    Some(lookup!($pow10, k) as $t)
+                } else {
+                    None
+                }
+            }
+
+            #[inline]
+            pub const fn saturating_pow10(k: u32) -> $t {
+                match checked_pow10(k) {
+                    Some(pow) => pow,
+                    None => <$t>::MAX,
+                }
+            }
+
+            // Safety: k must be at most MAX_LOG10.
+            // Like log10_floor_unchecked, the body is safe code without
+            // unsafe-fast: lookup! clamps a bigger k to the last power.
+            #[inline]
+            #[allow(unsafe_code)]
+            pub const unsafe fn pow10_unchecked(k: u32) -> $t {
+                debug_assert!(k <= MAX_LOG10, "pow10_unchecked overflowed");
+                // $pow10 has an entry for every k up to MAX_LOG10.
+                lookup!($pow10, k) as $t
+            }
+
+            // Each of these is one log10_floor and one comparison with, or
+            // read of, the entry of $pow10 it gives.
+
+            #[inline]
+            pub const fn is_power_of_ten(x: $t) -> bool {
+                // decimal_digits rather than log10_floor so that 0 doesn't
+                // panic; it compares with POW10[0], 1.
+                x as $u == lookup!($pow10, decimal_digits(x) - 1)
+            }
+
+            #[inline]
+            pub const fn checked_next_power_of_ten(x: $t) -> Option<$t> {
+                if x <= 1 {
+                    Some(1)
+                } else {
+                    checked_pow10(log10_floor(x - 1) + 1)
+                }
+            }
+
+            #[inline]
+            pub const fn next_power_of_ten(x: $t) -> $t {
+                match checked_next_power_of_ten(x) {
+                    Some(pow) => pow,
+                    None if cfg!(debug_assertions) => panic!("next_power_of_ten overflowed"),
+                    None => 0,
+                }
+            }
+
+            #[inline]
+            pub const fn prev_power_of_ten(x: $t) -> $t {
+                lookup!($pow10, log10_floor(x)) as $t
+            }
+
+            #[inline]
+            pub const fn div_pow10(x: $t, k: u32) -> $t {
+                if k as usize >= $pow10.len() {
+                    return 0;
+                }
+                if k == 0 {
+                    return x;
+                }
+                // Taking out the 2^k first leaves k bits spare, which is what
+                // lets the magic for 5^k fit in a $u, and the shift is at
+                // least the width of $u, so the high half is all that's needed.
+                let high = mul_high!($u, x as $u >> k, lookup!($magics, k));
+                (high >> (lookup!($shifts, k) as u32 - <$u>::BITS)) as $t
+            }
+
+            #[inline]
+            pub const fn rem_pow10(x: $t, k: u32) -> $t {
+                divrem_pow10(x, k).1
+            }
+
+            #[inline]
+            pub const fn divrem_pow10(x: $t, k: u32) -> ($t, $t) {
+                let quotient = div_pow10(x, k);
+                match checked_pow10(k) {
+                    Some(pow) => (quotient, x - quotient * pow),
+                    None => (0, x),
+                }
+            }
+        }
+
+        impl_int_log10_traits!($m, $t, $nz, |x| {
+            if x == 0 {
+                panic!("log10_floor of 0 is undefined");
+            }
+            S::$with(x as $u)
+        });
+
+        impl Pow10 for $t {
+            #[inline]
+            fn checked_pow10(k: u32) -> Option<$t> {
+                $m::checked_pow10(k)
+            }
+
+            #[inline]
+            fn saturating_pow10(k: u32) -> $t {
+                $m::saturating_pow10(k)
+            }
+
+            #[inline]
+            #[allow(unsafe_code)]
+            unsafe fn pow10_unchecked(k: u32) -> $t {
+                // SAFETY: Passed on to our caller.
+                unsafe { $m::pow10_unchecked(k) }
+            }
+
+            #[inline]
+            fn is_power_of_ten(self) -> bool {
+                $m::is_power_of_ten(self)
+            }
+
+            #[inline]
+            fn next_power_of_ten(self) -> $t {
+                $m::next_power_of_ten(self)
+            }
+
+            #[inline]
+            fn checked_next_power_of_ten(self) -> Option<$t> {
+                $m::checked_next_power_of_ten(self)
+            }
+
+            #[inline]
+            fn prev_power_of_ten(self) -> $t {
+                $m::prev_power_of_ten(self)
+            }
+
+            #[inline]
+            fn div_pow10(self, k: u32) -> $t {
+                $m::div_pow10(self, k)
+            }
+
+            #[inline]
+            fn rem_pow10(self, k: u32) -> $t {
+                $m::rem_pow10(self, k)
+            }
+
+            #[inline]
+            fn divrem_pow10(self, k: u32) -> ($t, $t) {
+                $m::divrem_pow10(self, k)
+            }
+        }
+    };
+}
+
+// The signed types just take the magnitude with unsigned_abs, which can't
+// overflow, and hand it to the unsigned type of the same width, $u, whose
+// module is $um.
+macro_rules! impl_int_log10_signed {
+    ($m:ident, $t:ident, $um:ident, $u:ident, $nz:ty) => {
+        pub mod $m {
+            use super::*;
+
+            // See IntLog10 for what these do.
+
+            // MIN has the biggest magnitude, which can have one more digit
+            // than MAX, though it never does for the widths there are.
+            pub const MAX_LOG10: u32 = log10_floor(<$t>::MIN);
+            pub const MAX_DIGITS: u32 = MAX_LOG10 + 1;
+            pub const MAX_DISPLAY_LEN: u32 = MAX_DIGITS + 1;
+
+            #[inline]
+            pub const fn log10_floor(x: $t) -> u32 {
+                $um::log10_floor(x.unsigned_abs())
+            }
+
+            #[inline]
+            pub const fn log10_ceil(x: $t) -> u32 {
+                $um::log10_ceil(x.unsigned_abs())
+            }
+
+            #[inline]
+            pub const fn log10_round(x: $t) -> u32 {
+                $um::log10_round(x.unsigned_abs())
+            }
+
+            #[inline]
+            pub const fn checked_log10_floor(x: $t) -> Option<u32> {
+                $um::checked_log10_floor(x.unsigned_abs())
+            }
+
+            #[inline]
+            pub const fn checked_log10_ceil(x: $t) -> Option<u32> {
+                $um::checked_log10_ceil(x.unsigned_abs())
+            }
+
+            #[inline]
+            pub const fn checked_log10_round(x: $t) -> Option<u32> {
+                $um::checked_log10_round(x.unsigned_abs())
+            }
+
+            #[inline]
+            pub const fn try_log10_floor(x: $t) -> Result<u32, Log10Error> {
+                if x < 0 {
+                    Err(Log10Error::Negative)
+                } else {
+                    $um::try_log10_floor(x as $u)
+                }
+            }
+
+            #[inline]
+            pub const fn try_log10_ceil(x: $t) -> Result<u32, Log10Error> {
+                if x < 0 {
+                    Err(Log10Error::Negative)
+                } else {
+                    $um::try_log10_ceil(x as $u)
+                }
+            }
+
+            #[inline]
+            pub const fn try_log10_round(x: $t) -> Result<u32, Log10Error> {
+                if x < 0 {
+                    Err(Log10Error::Negative)
+                } else {
+                    $um::try_log10_round(x as $u)
+                }
+            }
+
+            #[inline]
+            pub const fn ilog_floor<const B: u128>(x: $t) -> u32 {
+                $um::ilog_floor::<B>(x.unsigned_abs())
+            }
+
+            // See NonZeroLog10.
+            #[inline]
+            pub const fn log10_nonzero(x: $nz) -> u32 {
+                $um::log10_nonzero(x.unsigned_abs())
+            }
+
+            // Safety: x must not be 0.
+            #[inline]
+            #[allow(unsafe_code)]
+            pub const unsafe fn log10_floor_unchecked(x: $t) -> u32 {
+                // SAFETY: The caller promises x isn't 0, so neither is its magnitude.
+                unsafe { $um::log10_floor_unchecked(x.unsigned_abs()) }
+            }
+
+            // 10^k is below the magnitude of MIN, which is a power of two,
+            // so it fits in $t, and so does what's left after taking it off;
+            // both are then given the sign of x.
+
+            #[inline]
+            pub const fn log10_floor_with_pow(x: $t) -> (u32, $t) {
+                let (log, pow) = $um::log10_floor_with_pow(x.unsigned_abs());
+                if x < 0 {
+                    (log, -(pow as $t))
+                } else {
+                    (log, pow as $t)
+                }
+            }
+
+            #[inline]
+            pub const fn log10_floor_rem(x: $t) -> (u32, $t) {
+                let (log, rem) = $um::log10_floor_rem(x.unsigned_abs());
+                if x < 0 {
+                    (log, -(rem as $t))
+                } else {
+                    (log, rem as $t)
+                }
+            }
+
+            #[inline]
+            pub const fn decimal_digits(x: $t) -> u32 {
+                $um::decimal_digits(x.unsigned_abs())
+            }
+
+            #[inline]
+            pub const fn display_len(x: $t) -> u32 {
+                decimal_digits(x) + (x < 0) as u32
+            }
+
+            #[inline]
+            pub const fn decimal_trailing_zeros(x: $t) -> u32 {
+                $um::decimal_trailing_zeros(x.unsigned_abs())
+            }
+
+            // Stripping zeros only makes the magnitude smaller, and the
+            // magnitude of MIN, the one that doesn't fit in $t, is a power of
+            // two with no zeros to strip, so it comes back as MIN again.
+            #[inline]
+            pub const fn strip_decimal_zeros(x: $t) -> ($t, u32) {
+                let (mantissa, zeros) = $um::strip_decimal_zeros(x.unsigned_abs());
+                let mantissa = mantissa as $t;
+                if x < 0 {
+                    (mantissa.wrapping_neg(), zeros)
+                } else {
+                    (mantissa, zeros)
+                }
+            }
+        }
+
+        impl_int_log10_traits!($m, $t, $nz, |x| x.unsigned_abs().log10_floor_with::<S>());
+    };
+}
+
+// The trait impls are the same for every type, given its module, except for
+// log10_floor_with, whose body is passed in, with x for self and S for the strategy.
+macro_rules! impl_int_log10_traits {
+    ($m:ident, $t:ident, $nz:ty, |$x:ident| $with:expr) => {
+        impl IntLog10 for $t {
+            const MAX_LOG10: u32 = $m::MAX_LOG10;
+            const MAX_DIGITS: u32 = $m::MAX_DIGITS;
+            const MAX_DISPLAY_LEN: u32 = $m::MAX_DISPLAY_LEN;
+
+            #[inline]
+            fn log10_floor(self) -> u32 {
+                $m::log10_floor(self)
+            }
+
+            #[inline]
+            fn log10_ceil(self) -> u32 {
+                $m::log10_ceil(self)
+            }
+
+            #[inline]
+            fn log10_round(self) -> u32 {
+                $m::log10_round(self)
+            }
+
+            #[inline]
+            fn checked_log10_floor(self) -> Option<u32> {
+                $m::checked_log10_floor(self)
+            }
+
+            #[inline]
+            fn checked_log10_ceil(self) -> Option<u32> {
+                $m::checked_log10_ceil(self)
+            }
+
+            #[inline]
+            fn checked_log10_round(self) -> Option<u32> {
+                $m::checked_log10_round(self)
+            }
+
+            #[inline]
+            fn try_log10_floor(self) -> Result<u32, Log10Error> {
+                $m::try_log10_floor(self)
+            }
+
+            #[inline]
+            fn try_log10_ceil(self) -> Result<u32, Log10Error> {
+                $m::try_log10_ceil(self)
+            }
+
+            #[inline]
+            fn try_log10_round(self) -> Result<u32, Log10Error> {
+                $m::try_log10_round(self)
+            }
+
+            #[inline]
+            fn ilog_floor<const B: u128>(self) -> u32 {
+                $m::ilog_floor::<B>(self)
+            }
+
+            #[inline]
+            #[allow(unsafe_code)]
+            unsafe fn log10_floor_unchecked(self) -> u32 {
+                // SAFETY: Passed on to our caller.
+                unsafe { $m::log10_floor_unchecked(self) }
+            }
+
+            #[inline]
+            fn log10_floor_with<S: Strategy>(self) -> u32 {
+                let $x = self;
+                $with
+            }
+
+            #[inline]
+            fn log10_floor_with_pow(self) -> (u32, $t) {
+                $m::log10_floor_with_pow(self)
+            }
+
+            #[inline]
+            fn log10_floor_rem(self) -> (u32, $t) {
+                $m::log10_floor_rem(self)
+            }
+
+            #[inline]
+            fn decimal_digits(self) -> u32 {
+                $m::decimal_digits(self)
+            }
+
+            #[inline]
+            fn display_len(self) -> u32 {
+                $m::display_len(self)
+            }
+
+            #[inline]
+            fn decimal_trailing_zeros(self) -> u32 {
+                $m::decimal_trailing_zeros(self)
+            }
+
+            #[inline]
+            fn strip_decimal_zeros(self) -> ($t, u32) {
+                $m::strip_decimal_zeros(self)
+            }
+        }
+
+        impl NonZeroLog10 for $nz {
+            #[inline]
+            fn log10(self) -> u32 {
+                $m::log10_nonzero(self)
+            }
+        }
+    };
+}
+
+impl_int_log10!(
+    for_u8, u8, u8, NonZeroU8, POW10_U8, LIMITS_U8, MIDS_U8,
+    DIV_MAGICS_U8, DIV_SHIFTS_U8,
+    INV5S_U8, QUOTIENTS_U8, log10_u8
+);
+impl_int_log10!(
+    for_u16, u16, u16, NonZeroU16, POW10_U16, LIMITS_U16, MIDS_U16,
+    DIV_MAGICS_U16, DIV_SHIFTS_U16,
+    INV5S_U16, QUOTIENTS_U16, log10_u16
+);
+impl_int_log10!(
+    for_u32, u32, u32, NonZeroU32, POW10_U32, LIMITS_U32, MIDS_U32,
+    DIV_MAGICS_U32, DIV_SHIFTS_U32,
+    INV5S_U32, QUOTIENTS_U32, log10_u32
+);
+impl_int_log10!(
+    for_u64, u64, u64, NonZeroU64, POW10_U64, LIMITS_U64, MIDS_U64,
+    DIV_MAGICS_U64, DIV_SHIFTS_U64,
+    INV5S_U64, QUOTIENTS_U64, log10_u64
+);
+impl_int_log10!(
+    for_u128, u128, u128, NonZeroU128, POW10_U128, LIMITS_U128, MIDS_U128,
+    DIV_MAGICS_U128, DIV_SHIFTS_U128,
+    INV5S_U128, QUOTIENTS_U128, log10_u128
+);
+
+#[cfg(target_pointer_width = "16")]
+impl_int_log10!(
+    for_usize, usize, u16, NonZeroUsize, POW10_U16, LIMITS_U16, MIDS_U16,
+    DIV_MAGICS_U16, DIV_SHIFTS_U16,
+    INV5S_U16, QUOTIENTS_U16, log10_u16
+);
+#[cfg(target_pointer_width = "32")]
+impl_int_log10!(
+    for_usize, usize, u32, NonZeroUsize, POW10_U32, LIMITS_U32, MIDS_U32,
+    DIV_MAGICS_U32, DIV_SHIFTS_U32,
+    INV5S_U32, QUOTIENTS_U32, log10_u32
+);
+#[cfg(target_pointer_width = "64")]
+impl_int_log10!(
+    for_usize, usize, u64, NonZeroUsize, POW10_U64, LIMITS_U64, MIDS_U64,
+    DIV_MAGICS_U64, DIV_SHIFTS_U64,
+    INV5S_U64, QUOTIENTS_U64, log10_u64
+);
+
+impl_int_log10_signed!(for_i8, i8, for_u8, u8, NonZeroI8);
+impl_int_log10_signed!(for_i16, i16, for_u16, u16, NonZeroI16);
+impl_int_log10_signed!(for_i32, i32, for_u32, u32, NonZeroI32);
+impl_int_log10_signed!(for_i64, i64, for_u64, u64, NonZeroI64);
+impl_int_log10_signed!(for_i128, i128, for_u128, u128, NonZeroI128);
+impl_int_log10_signed!(for_isize, isize, for_usize, usize, NonZeroIsize);
+
+// Floats are done the same way as integers: floor(log2(x)) comes from the
+// exponent, it gives a guess at floor(log10(x)) that might be one too low,
+// and the bits of x get compared with those of the highest float with that
+// guess as its floor(log10), which works since the bits of positive floats are
+// in the same order as the floats.  Float bits are never compared with powers
+// of ten directly, which mostly aren't floats, so the answers are exact.
+//
+// The guess is floor(log10(2^log2)), which needs no table: floor_log10_pow2,
+// below, is exact for every floor(log2(x)) a positive finite f64 can have, from
+// that of the smallest subnormal, 2^-1074, up to 1023, and so for f32 too.
+
+// Like the integer types, each float type gets a module of const fns, for_f32
+// and for_f64, and the trait methods just call them.  $bits is the unsigned
+// type of the same width.
+macro_rules! impl_float_log10 {
+    ($m:ident, $t:ident, $bits:ident) => {
+        pub mod $m {
+            use super::*;
+
+            // The smallest subnormal is 2^E_MIN, and the guesses run from
+            // GUESS_MIN, for it, to GUESS_MAX, for 2^(MAX_EXP - 1).
+            const E_MIN: i32 = <$t>::MIN_EXP - <$t>::MANTISSA_DIGITS as i32;
+            const GUESS_MIN: i32 = floor_log10_pow2(E_MIN);
+            const GUESS_MAX: i32 = floor_log10_pow2(<$t>::MAX_EXP - 1);
+            const GUESSES: usize = (GUESS_MAX - GUESS_MIN + 1) as usize;
+
+            // LIMITS[log - GUESS_MIN] is the bits of the highest x for which
+            // floor(log10(x)) == log, for every guess.  When there's no float as
+            // big as 10^(log + 1), it's the bits of MAX instead, so the guess
+            // stands; that's the last entry for f32, but f64 gets close enough
+            // to 10^309 to need no such entry.
+            const LIMITS: [$bits; GUESSES] = {
+                const WIDE: [u128; GUESSES] = make_float_limits(
+                    <$t>::MANTISSA_DIGITS,
+                    E_MIN,
+                    <$t>::MAX.to_bits() as u128,
+                    GUESS_MIN,
+                );
+                assert!(float_limits_ok(
+                    <$t>::MANTISSA_DIGITS,
+                    E_MIN,
+                    <$t>::MAX.to_bits() as u128,
+                    GUESS_MIN,
+                    &WIDE
+                ));
+                narrow!($bits, WIDE)
+            };
+
+            // See FloatLog10 for what these do.
+
+            // The smallest subnormal is exactly 2^E_MIN, so its guess is right.
+            pub const MIN_LOG10: i32 = GUESS_MIN;
+            pub const MAX_LOG10: i32 = log10_floor(<$t>::MAX);
+
+            #[inline]
+            pub const fn checked_log10_floor(x: $t) -> Option<i32> {
+                // The bits of the magnitude.  Anything past those of MAX is
+                // infinity or NaN.
+                let bits = x.to_bits() & !(1 << ($bits::BITS - 1));
+                if bits == 0 || bits > <$t>::MAX.to_bits() {
+                    return None;
+                }
+                // For a normal x the exponent field gives floor(log2(x)); for a
+                // subnormal, it's where the top bit of the significand is.
+                let field = (bits >> (<$t>::MANTISSA_DIGITS - 1)) as i32;
+                let log2x = if field == 0 {
+                    E_MIN + ($bits::BITS - 1 - bits.leading_zeros()) as i32
+                } else {
+                    E_MIN + field - 1 + (<$t>::MANTISSA_DIGITS - 1) as i32
+                };
+                // log2x is from E_MIN to MAX_EXP - 1, which floor_log10_pow2 covers.
+                let guess = floor_log10_pow2(log2x);
+                // LIMITS has an entry for every guess from GUESS_MIN to GUESS_MAX.
+                let limit = lookup!(LIMITS, guess - GUESS_MIN);
+                if bits > limit {
+                    Some(guess + 1)
+                } else {
+                    Some(guess)
+                }
+            }
+
+            #[inline]
+            pub const fn log10_floor(x: $t) -> i32 {
+                match checked_log10_floor(x) {
+                    Some(log) => log,
+                    None => panic!("log10_floor of 0, infinity or NaN is undefined"),
+                }
+            }
+        }
+
+        impl FloatLog10 for $t {
+            const MIN_LOG10: i32 = $m::MIN_LOG10;
+            const MAX_LOG10: i32 = $m::MAX_LOG10;
+
+            #[inline]
+            fn log10_floor(self) -> i32 {
+                $m::log10_floor(self)
+            }
+
+            #[inline]
+            fn checked_log10_floor(self) -> Option<i32> {
+                $m::checked_log10_floor(self)
+            }
+        }
     };
-    let limit = unsafe {
-        // SAFETY: Indices come from LOG10S_FOR_LOG2S,
-        // and we made sure we have an entry for each.
-        *(&LIMITS).get_unchecked(log10x_guess as usize)
+}
+
+impl_float_log10!(for_f32, f32, u32);
+impl_float_log10!(for_f64, f64, u64);
+
+// Shortest round-trip float printers, like Ryu, Grisu and Dragonbox, need
+// these for exponents in a bounded range.  Each one is a multiply by a
+// fixed-point logarithm and a shift, which is exact for every e in its range;
+// outside it the answer can be off, and builds with debug assertions panic.
+// The shifts are arithmetic, so they round down for negative e too.
+
+// floor(e * log10(2)), i.e. floor(log10(2^e)), for -2620 <= e <= 2620.
+// 315_653 is log10(2) * 2^20, 315_652.83, rounded up; rounded down, it's too
+// small to be exact over the whole range.
+pub const fn floor_log10_pow2(e: i32) -> i32 {
+    debug_assert!(-2620 <= e && e <= 2620, "floor_log10_pow2 out of range");
+    (e * 315_653) >> 20
+}
+
+// floor(e * log10(5)), i.e. floor(log10(5^e)), for -2620 <= e <= 2620.
+// 732_923 is log10(5) * 2^20, rounded down.
+pub const fn floor_log10_pow5(e: i32) -> i32 {
+    debug_assert!(-2620 <= e && e <= 2620, "floor_log10_pow5 out of range");
+    (e * 732_923) >> 20
+}
+
+// floor(log2(10^e)), i.e. floor(e * log2(10)), for -1233 <= e <= 1233.
+// 1_741_647 is log2(10) * 2^19, rounded down; the range is as far as e can go
+// before the product overflows an i32.
+pub const fn floor_log2_pow10(e: i32) -> i32 {
+    debug_assert!(-1233 <= e && e <= 1233, "floor_log2_pow10 out of range");
+    (e * 1_741_647) >> 19
+}
+
+// Check every e in the ranges above, using the lengths of powers of five.
+const _: () = {
+    let mut e = -2620;
+    while e <= 2620 {
+        assert!(log10_pow2_ok(e, floor_log10_pow2(e)));
+        assert!(log10_pow5_ok(e, floor_log10_pow5(e)));
+        if -1233 <= e && e <= 1233 {
+            assert!(log2_pow10_ok(e, floor_log2_pow10(e)));
+        }
+        e += 1;
+    }
+};
+
+// Logarithms to other bases work the same way, except that the tables depend on
+// the base, so they're associated consts of BaseTables<B> and get built at
+// compile time for each base that's actually used.  They're all u128 so that one
+// pair of tables serves every width; a base only ever used with u32 could get
+// away with narrower limits, but that's not worth a table per width per base.
+struct BaseTables<const B: u128>;
+
+impl<const B: u128> BaseTables<B> {
+    // GUESSES[n] is floor(log_B(2^n)), the same as LOG10S_FOR_LOG2S for base 10.
+    const GUESSES: [u8; 128] = {
+        let guesses = make_guesses(B);
+        assert!(guesses_ok(B, &guesses));
+        guesses
+    };
+
+    // LIMITS[log] is the highest x for which floor(log_B(x)) == log, or u128::MAX
+    // once B^(log + 1) - 1 doesn't fit.  There are more entries than any base
+    // needs; base 2 is the worst case at 128.
+    const LIMITS: [u128; 128] = {
+        let limits = make_limits(B, u128::MAX);
+        assert!(limits_ok(B, 128, &Self::GUESSES, &limits, u128::MAX));
+        limits
     };
+}
+
+// The guess-and-correct step for base B, with x widened to u128.
+// x must not be 0, and log2x must really be floor(log2(x)).
+#[inline(always)]
+const fn ilog_floor_from_log2<const B: u128>(x: u128, log2x: u32) -> u32 {
+    // log2 of a u128 is at most 127.
+    let guess = lookup!(BaseTables::<B>::GUESSES, log2x);
+    // Guesses are at most 127, and LIMITS has 128 entries.
+    let limit = lookup!(BaseTables::<B>::LIMITS, guess);
     if x > limit {
-        log10x_guess + 1
+        guess as u32 + 1
     } else {
-        log10x_guess
+        guess as u32
     }
 }
 
-/*
-// Safe version.
-pub fn log10_floor(x: u16) -> u8 {
-    let log2x = x.log2() as usize;
-    let log10x_guess = LOG10S_FOR_LOG2S[log2x];
-    if x > LIMITS[log10x_guess as usize] {
-        log10x_guess + 1
+// Returns the floor of log base B of the magnitude of x, e.g. ilog_floor::<3>(x).
+// This is just IntLog10::ilog_floor written as a function, so it can't be
+// const; use e.g. for_u32::ilog_floor::<3>(x) for that.
+pub fn ilog_floor<const B: u128>(x: impl IntLog10) -> u32 {
+    x.ilog_floor::<B>()
+}
+
+// Pow10's functions written as functions, e.g. checked_pow10::<u32>(k).
+// Like ilog_floor these can't be const; use e.g. for_u32::checked_pow10(k) for that.
+pub fn checked_pow10<T: Pow10>(k: u32) -> Option<T> {
+    T::checked_pow10(k)
+}
+
+pub fn saturating_pow10<T: Pow10>(k: u32) -> T {
+    T::saturating_pow10(k)
+}
+
+// Safety: k must be at most T's IntLog10::MAX_LOG10.
+#[inline]
+#[allow(unsafe_code)]
+pub unsafe fn pow10_unchecked<T: Pow10>(k: u32) -> T {
+    // SAFETY: Passed on to our caller.
+    unsafe { T::pow10_unchecked(k) }
+}
+
+// Returns the floor of log base 10 of its argument.
+// This is the original u16-only entry point; see IntLog10 for the other types.
+// This routine uses floor(log2(x)), from leading_zeros, in order to get good
+// performance; on modern architectures there is typically a fairly quick
+// instruction for that.
+pub const fn log10_floor(x: u16) -> u8 {
+    for_u16::log10_floor(x) as u8
+}
+
+// Like log10_u32, but returns None for 0 rather than indexing off the front
+// of the table.
+pub const fn checked_log10_u32(x: u32) -> Option<u32> {
+    if x == 0 {
+        None
     } else {
-        log10x_guess
+        Some(log10_u32(x))
     }
 }
- */
 
 // From jhpratt https://github.com/rust-lang/rust/issues/70887
+// x must not be 0.
 pub const fn log10_u32(x: u32) -> u32 {
     const TABLE: &[u64] = &[
         0x0000_0000_0000,
@@ -120,9 +1364,85 @@ pub const fn log10_u32(x: u32) -> u32 {
     ((x as u64 + TABLE[31 - x.leading_zeros() as usize]) >> 32) as _
 }
 
+// The same trick for u64, using u128 arithmetic so that there are 64 bits
+// above x for the guess to sit in and the carry to land in.
+// Entry n is (guess << 64) + the addend, as make_carries describes it.
+const CARRIES_U64: [u128; 64] = {
+    let carries = make_carries(10, 64);
+    let mut table = [0; 64];
+    let mut n = 0;
+    while n < 64 {
+        table[n] = ((LOG10S_FOR_LOG2S[n] as u128) << 64) + carries[n];
+        n += 1;
+    }
+    table
+};
+
+// x must not be 0.
+pub const fn log10_u64(x: u64) -> u32 {
+    ((x as u128 + CARRIES_U64[63 - x.leading_zeros() as usize]) >> 64) as _
+}
+
+pub const fn checked_log10_u64(x: u64) -> Option<u32> {
+    if x == 0 {
+        None
+    } else {
+        Some(log10_u64(x))
+    }
+}
+
+// For u128 there's no wider type, so the guess comes from LOG10S_FOR_LOG2S and
+// the carry from overflowing_add; it's still branch-free.
+const CARRIES_U128: [u128; 128] = make_carries(10, 128);
+
+// x must not be 0.
+pub const fn log10_u128(x: u128) -> u32 {
+    let n = 127 - x.leading_zeros() as usize;
+    let (_, carry) = x.overflowing_add(CARRIES_U128[n]);
+    LOG10S_FOR_LOG2S[n] as u32 + carry as u32
+}
+
+pub const fn checked_log10_u128(x: u128) -> Option<u32> {
+    if x == 0 {
+        None
+    } else {
+        Some(log10_u128(x))
+    }
+}
+
+// Make sure the carries land where they should, at both ends of every range
+// of x with the same log2 and on both sides of every power of ten.
+const _: () = {
+    let mut n = 0;
+    while n < 128 {
+        let low = 1u128 << n;
+        let high = low | (low - 1);
+        assert!(log10_u128(low) == for_u128::log10_floor(low));
+        assert!(log10_u128(high) == for_u128::log10_floor(high));
+        if n < 64 {
+            assert!(log10_u64(low as u64) == for_u64::log10_floor(low as u64));
+            assert!(log10_u64(high as u64) == for_u64::log10_floor(high as u64));
+        }
+        n += 1;
+    }
+    let mut pow = 10u128;
+    let mut log = 1;
+    while let Some(next) = pow.checked_mul(10) {
+        assert!(log10_u128(pow - 1) == log - 1 && log10_u128(pow) == log);
+        if pow <= u64::MAX as u128 {
+            assert!(log10_u64(pow as u64 - 1) == log - 1 && log10_u64(pow as u64) == log);
+        }
+        pow = next;
+        log += 1;
+    }
+};
+
 #[cfg(test)]
 mod tests {
+    extern crate std;
+
     use super::*;
+    use std::string::ToString;
 
     #[test]
     #[should_panic]
@@ -144,4 +1464,623 @@ mod tests {
         assert_eq!(log10_floor(u16::MAX), 4);
     }
 
+    #[test]
+    fn test_widths() {
+        assert_eq!(9u8.log10_floor(), 0);
+        assert_eq!(10u8.log10_floor(), 1);
+        assert_eq!(u8::MAX.log10_floor(), 2);
+        assert_eq!(u16::MAX.log10_floor(), 4);
+        assert_eq!(u32::MAX.log10_floor(), 9);
+        assert_eq!(u64::MAX.log10_floor(), 19);
+        assert_eq!(u128::MAX.log10_floor(), 38);
+        assert_eq!(usize::MAX.log10_floor(), (usize::MAX as u64).log10_floor());
+
+        let mut pow = 1u128;
+        for log in 0..=38 {
+            if log > 0 {
+                assert_eq!((pow - 1).log10_floor(), log - 1);
+            }
+            assert_eq!(pow.log10_floor(), log);
+            if pow <= u64::MAX as u128 {
+                assert_eq!((pow as u64).log10_floor(), log);
+                assert_eq!(log10_u64(pow as u64), log);
+                if log > 0 {
+                    assert_eq!((pow as u64 - 1).log10_floor(), log - 1);
+                    assert_eq!(log10_u64(pow as u64 - 1), log - 1);
+                }
+            }
+            if pow <= u32::MAX as u128 {
+                assert_eq!((pow as u32).log10_floor(), log);
+            }
+            pow = pow.saturating_mul(10);
+        }
+    }
+
+    #[test]
+    fn test_signed() {
+        assert_eq!(1i8.log10_floor(), 0);
+        assert_eq!((-1i8).log10_floor(), 0);
+        assert_eq!((-10i16).log10_floor(), 1);
+        assert_eq!((-9_999i32).log10_floor(), 3);
+        assert_eq!(i8::MIN.log10_floor(), 2);
+        assert_eq!(i32::MIN.log10_floor(), 9);
+        assert_eq!(i64::MIN.log10_floor(), 18);
+        assert_eq!(i128::MAX.log10_floor(), 38);
+        assert_eq!(i128::MIN.log10_floor(), 38);
+        assert_eq!((-1_000isize).log10_floor(), 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_signed0() {
+        0i64.log10_floor();
+    }
+
+    #[test]
+    fn test_ceil() {
+        assert_eq!(1u8.log10_ceil(), 0);
+        assert_eq!(2u8.log10_ceil(), 1);
+        assert_eq!(10u8.log10_ceil(), 1);
+        assert_eq!(11u8.log10_ceil(), 2);
+        assert_eq!(u8::MAX.log10_ceil(), 3);
+        assert_eq!(1_000u16.log10_ceil(), 3);
+        assert_eq!(1_001u16.log10_ceil(), 4);
+        assert_eq!(u64::MAX.log10_ceil(), 20);
+        assert_eq!(u128::MAX.log10_ceil(), 39);
+        assert_eq!((-100i32).log10_ceil(), 2);
+        assert_eq!(i128::MIN.log10_ceil(), 39);
+    }
+
+    #[test]
+    #[should_panic(expected = "log10_ceil of 0")]
+    fn test_ceil0() {
+        0u32.log10_ceil();
+    }
+
+    #[test]
+    #[should_panic(expected = "log10_ceil of 0")]
+    fn test_signed_ceil0() {
+        0i64.log10_ceil();
+    }
+
+    #[test]
+    fn test_round() {
+        assert_eq!(1u8.log10_round(), 0);
+        assert_eq!(3u8.log10_round(), 0);
+        assert_eq!(4u8.log10_round(), 1);
+        assert_eq!(31u8.log10_round(), 1);
+        assert_eq!(32u8.log10_round(), 2);
+        assert_eq!(u8::MAX.log10_round(), 2);
+        assert_eq!(31_622u16.log10_round(), 4);
+        assert_eq!(31_623u16.log10_round(), 5);
+        assert_eq!(3_162_277_660u32.log10_round(), 9);
+        assert_eq!(3_162_277_661u32.log10_round(), 10);
+        assert_eq!(u64::MAX.log10_round(), 19);
+        assert_eq!(316_227_766_016_837_933_199_889_354_443_271_853_371u128.log10_round(), 38);
+        assert_eq!(u128::MAX.log10_round(), 39);
+        assert_eq!((-4i8).log10_round(), 1);
+    }
+
+    #[test]
+    fn test_checked() {
+        assert_eq!(0u8.checked_log10_floor(), None);
+        assert_eq!(0u128.checked_log10_ceil(), None);
+        assert_eq!(0i32.checked_log10_round(), None);
+        assert_eq!(100u16.checked_log10_floor(), Some(2));
+        assert_eq!(101u16.checked_log10_ceil(), Some(3));
+        assert_eq!((-400i64).checked_log10_round(), Some(3));
+        assert_eq!(checked_log10_u32(0), None);
+        assert_eq!(checked_log10_u32(u32::MAX), Some(9));
+    }
+
+    #[test]
+    fn test_try() {
+        assert_eq!(0u32.try_log10_floor(), Err(Log10Error::Zero));
+        assert_eq!(0i32.try_log10_ceil(), Err(Log10Error::Zero));
+        assert_eq!((-5i32).try_log10_round(), Err(Log10Error::Negative));
+        assert_eq!(i128::MIN.try_log10_floor(), Err(Log10Error::Negative));
+        assert_eq!(i128::MAX.try_log10_floor(), Ok(38));
+        assert_eq!(99usize.try_log10_ceil(), Ok(2));
+        assert_eq!(Log10Error::Zero.to_string(), "logarithm of zero");
+    }
+
+    #[test]
+    fn test_nonzero() {
+        assert_eq!(NonZeroU8::new(1).unwrap().log10(), 0);
+        assert_eq!(NonZeroU8::MAX.log10(), 2);
+        assert_eq!(NonZeroU16::new(10_000).unwrap().log10(), 4);
+        assert_eq!(NonZeroU32::new(999_999_999).unwrap().log10(), 8);
+        assert_eq!(NonZeroU64::MAX.log10(), 19);
+        assert_eq!(NonZeroU128::MAX.log10(), 38);
+        assert_eq!(NonZeroUsize::new(100).unwrap().log10(), 2);
+        assert_eq!(NonZeroI8::MIN.log10(), 2);
+        assert_eq!(NonZeroI32::new(-1_000).unwrap().log10(), 3);
+        assert_eq!(NonZeroI128::MIN.log10(), 38);
+        assert_eq!(NonZeroIsize::new(-9).unwrap().log10(), 0);
+    }
+
+    #[test]
+    fn test_ilog() {
+        assert_eq!(ilog_floor::<10>(u32::MAX), 9);
+        assert_eq!(ilog_floor::<2>(1u8), 0);
+        assert_eq!(ilog_floor::<2>(u128::MAX), 127);
+        assert_eq!(ilog_floor::<3>(8u8), 1);
+        assert_eq!(ilog_floor::<3>(9u8), 2);
+        assert_eq!(ilog_floor::<3>(u8::MAX), 5);
+        assert_eq!(ilog_floor::<7>(-49i32), 2);
+        assert_eq!(ilog_floor::<36>(35u64), 0);
+        assert_eq!(ilog_floor::<36>(36u64), 1);
+        assert_eq!(ilog_floor::<1000>(999_999u32), 1);
+        assert_eq!(ilog_floor::<1000>(1_000_000u32), 2);
+        assert_eq!(ilog_floor::<1000>(u128::MAX), 12);
+        assert_eq!(ilog_floor::<{ u128::MAX }>(u128::MAX), 1);
+        assert_eq!(ilog_floor::<{ u128::MAX }>(u128::MAX - 1), 0);
+
+        fn check_base<const B: u128>() {
+            let mut pow = B;
+            let mut log = 1;
+            while let Some(next) = pow.checked_mul(B) {
+                assert_eq!((pow - 1).ilog_floor::<B>(), log - 1);
+                assert_eq!(pow.ilog_floor::<B>(), log);
+                if pow <= u32::MAX as u128 {
+                    assert_eq!((pow as u32).ilog_floor::<B>(), log);
+                }
+                pow = next;
+                log += 1;
+            }
+        }
+        check_base::<2>();
+        check_base::<3>();
+        check_base::<7>();
+        check_base::<10>();
+        check_base::<36>();
+        check_base::<1000>();
+    }
+
+    #[test]
+    fn test_const() {
+        const U64_DIGITS: usize = for_u64::log10_floor(u64::MAX) as usize + 1;
+        const I8_DIGITS: usize = for_i8::log10_floor(i8::MIN) as usize + 1;
+        let buf = [0u8; U64_DIGITS];
+        assert_eq!(buf.len(), 20);
+        assert_eq!(I8_DIGITS, 3);
+        const CEIL: u32 = for_u32::log10_ceil(1_001);
+        const ROUND: u32 = for_usize::log10_round(4);
+        const BASE3: u32 = for_u16::ilog_floor::<3>(81);
+        const CHECKED: Option<u32> = for_i128::checked_log10_floor(0);
+        const TRIED: Result<u32, Log10Error> = for_i32::try_log10_floor(-1);
+        const NONZERO: u32 = for_u128::log10_nonzero(NonZeroU128::MAX);
+        const OLD: u8 = log10_floor(10_000);
+        assert_eq!((CEIL, ROUND, BASE3, CHECKED), (4, 1, 4, None));
+        assert_eq!((TRIED, NONZERO, OLD), (Err(Log10Error::Negative), 38, 4));
+    }
+
+    #[test]
+    fn test_carries() {
+        assert_eq!(log10_u64(1), 0);
+        assert_eq!(log10_u64(9), 0);
+        assert_eq!(log10_u64(10), 1);
+        assert_eq!(log10_u64(u64::MAX), 19);
+        assert_eq!(log10_u128(1), 0);
+        assert_eq!(log10_u128(99), 1);
+        assert_eq!(log10_u128(100), 2);
+        assert_eq!(log10_u128(u128::MAX), 38);
+        assert_eq!(checked_log10_u64(0), None);
+        assert_eq!(checked_log10_u128(0), None);
+        assert_eq!(checked_log10_u128(10_000), Some(4));
+    }
+
+    #[test]
+    fn test_strategies() {
+        use strategy::*;
+
+        fn check<S: Strategy>() {
+            let mut pow = 1u128;
+            for _ in 0..=38 {
+                for x in [pow, pow + 1, pow * 2, pow.saturating_mul(10) - 1] {
+                    let expected = x.log10_floor();
+                    assert_eq!(x.log10_floor_with::<S>(), expected);
+                    if x <= u64::MAX as u128 {
+                        assert_eq!((x as u64).log10_floor_with::<S>(), expected);
+                        assert_eq!((x as usize).log10_floor_with::<S>(), expected);
+                    }
+                    if x <= i64::MAX as u128 {
+                        assert_eq!((-(x as i64)).log10_floor_with::<S>(), expected);
+                    }
+                    if x <= u32::MAX as u128 {
+                        assert_eq!((x as u32).log10_floor_with::<S>(), expected);
+                    }
+                    if x <= u16::MAX as u128 {
+                        assert_eq!((x as u16).log10_floor_with::<S>(), expected);
+                    }
+                    if x <= u8::MAX as u128 {
+                        assert_eq!((x as u8).log10_floor_with::<S>(), expected);
+                    }
+                }
+                pow = pow.saturating_mul(10);
+            }
+            assert_eq!(u128::MAX.log10_floor_with::<S>(), 38);
+            assert_eq!(u64::MAX.log10_floor_with::<S>(), 19);
+            assert_eq!(i8::MIN.log10_floor_with::<S>(), 2);
+        }
+        check::<TwoTable>();
+        check::<CarryTable>();
+        check::<BinarySearch>();
+        check::<Linear>();
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_strategy0() {
+        0u32.log10_floor_with::<strategy::Linear>();
+    }
+
+    #[test]
+    fn test_reference() {
+        for x in (1..=100_000u32).chain([u32::MAX - 1, u32::MAX]) {
+            let wide = x as u128;
+            assert_eq!(x.log10_floor(), reference::log10_floor(wide), "{}", x);
+            assert_eq!(x.log10_ceil(), reference::log10_ceil(wide), "{}", x);
+            assert_eq!(x.log10_round(), reference::log10_round(wide), "{}", x);
+            assert_eq!(x.ilog_floor::<7>(), reference::ilog_floor(7, wide), "{}", x);
+            assert_eq!(x.decimal_digits(), reference::decimal_digits(wide), "{}", x);
+        }
+        for x in [u64::MAX as u128, 3_162_277_660_168_379_331, 3_162_277_660_168_379_332] {
+            assert_eq!((x as u64).log10_round(), reference::log10_round(x), "{}", x);
+        }
+        let mid = 316_227_766_016_837_933_199_889_354_443_271_853_371u128;
+        assert_eq!(reference::log10_round(mid), 38);
+        assert_eq!(reference::log10_round(mid + 1), 39);
+        assert_eq!(reference::log10_round(u128::MAX), 39);
+        assert_eq!(reference::log10_ceil(u128::MAX), 39);
+        assert_eq!(reference::ilog_floor(u128::MAX, u128::MAX), 1);
+        // 1e23 is just below 10^23 as an f64, and 0.1 just above 10^-1.
+        assert_eq!(reference::float_log10_floor(1e23), 22);
+        assert_eq!(reference::float_log10_floor(0.1), -1);
+        assert_eq!(reference::float_log10_floor(-1.0), 0);
+        assert_eq!(reference::float_log10_floor(f64::MAX), 308);
+        assert_eq!(reference::float_log10_floor(5e-324), -324);
+    }
+
+    #[test]
+    #[allow(unsafe_code)]
+    fn test_unchecked() {
+        unsafe {
+            assert_eq!(1u8.log10_floor_unchecked(), 0);
+            assert_eq!(u16::MAX.log10_floor_unchecked(), 4);
+            assert_eq!(999_999u32.log10_floor_unchecked(), 5);
+            assert_eq!(u64::MAX.log10_floor_unchecked(), 19);
+            assert_eq!(u128::MAX.log10_floor_unchecked(), 38);
+            assert_eq!(100usize.log10_floor_unchecked(), 2);
+            assert_eq!(i8::MIN.log10_floor_unchecked(), 2);
+            assert_eq!(i128::MIN.log10_floor_unchecked(), 38);
+            assert_eq!((-10isize).log10_floor_unchecked(), 1);
+        }
+        const LOG: u32 = unsafe { for_u32::log10_floor_unchecked(1_000) };
+        assert_eq!(LOG, 3);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    #[allow(unsafe_code)]
+    fn test_unchecked0() {
+        unsafe {
+            0u32.log10_floor_unchecked();
+        }
+    }
+
+    #[test]
+    fn test_digits() {
+        assert_eq!(0u8.decimal_digits(), 1);
+        assert_eq!(0i32.display_len(), 1);
+        assert_eq!(reference::decimal_digits(0), 1);
+        assert_eq!(reference::display_len(0, false), 1);
+        assert_eq!(reference::display_len(128, true), i8::MIN.display_len());
+        for x in [1u64, 9, 10, 11, 99, 100, 1_000_000, u64::MAX - 1, u64::MAX] {
+            assert_eq!(x.display_len() as usize, x.to_string().len(), "{}", x);
+            let x = x as i64;
+            assert_eq!(x.display_len() as usize, x.to_string().len(), "{}", x);
+            assert_eq!((-x).display_len() as usize, (-x).to_string().len(), "{}", x);
+            assert_eq!((-x).decimal_digits(), x.decimal_digits(), "{}", x);
+        }
+        assert_eq!(i8::MIN.display_len(), 4);
+        assert_eq!(i128::MIN.display_len(), 40);
+        assert_eq!(u128::MAX.decimal_digits(), 39);
+        const BUF: [u8; for_u32::decimal_digits(u32::MAX) as usize] = [0; 10];
+        assert_eq!(BUF.len(), 10);
+    }
+
+    #[test]
+    fn test_max_consts() {
+        assert_eq!(<u8 as IntLog10>::MAX_DIGITS, 3);
+        assert_eq!(<u64 as IntLog10>::MAX_DIGITS, 20);
+        assert_eq!(<u128 as IntLog10>::MAX_DIGITS, 39);
+        assert_eq!(<i64 as IntLog10>::MAX_LOG10, 18);
+        assert_eq!(<i64 as IntLog10>::MAX_DIGITS, 19);
+        assert_eq!(<i64 as IntLog10>::MAX_DISPLAY_LEN, 20);
+        assert_eq!(u16::MAX_LOG10, LIMITS_U16.iter().position(|&l| l == u16::MAX).unwrap() as u32);
+        assert_eq!(usize::MAX_DIGITS as usize, usize::MAX.to_string().len());
+        assert_eq!(i128::MAX_DISPLAY_LEN as usize, i128::MIN.to_string().len());
+        let buf = [0u8; <u32 as IntLog10>::MAX_DISPLAY_LEN as usize];
+        assert_eq!(buf.len(), u32::MAX.to_string().len());
+    }
+
+    #[test]
+    #[allow(unsafe_code)]
+    fn test_pow10() {
+        assert_eq!(POW10_U16, [1, 10, 100, 1_000, 10_000]);
+        assert_eq!(POW10_U8.len() as u32, u8::MAX_DIGITS);
+        assert_eq!(POW10_U64.len() as u32, u64::MAX_DIGITS);
+        assert_eq!(POW10_U128.len() as u32, u128::MAX_DIGITS);
+        for k in 0..=40 {
+            let expected = reference::checked_pow10(k);
+            assert_eq!(for_u128::checked_pow10(k), expected, "{}", k);
+            assert_eq!(checked_pow10::<u32>(k).map(u128::from), expected.filter(|&p| p <= u32::MAX as u128));
+            assert_eq!(checked_pow10::<u64>(k), 10u64.checked_pow(k), "{}", k);
+            assert_eq!(saturating_pow10::<u16>(k), 10u16.saturating_pow(k), "{}", k);
+            assert_eq!(saturating_pow10::<usize>(k), 10usize.saturating_pow(k), "{}", k);
+            if k <= u64::MAX_LOG10 {
+                assert_eq!(unsafe { pow10_unchecked::<u64>(k) }, 10u64.pow(k), "{}", k);
+            }
+        }
+        for (log, &limit) in LIMITS_U32.iter().enumerate().take(9) {
+            assert_eq!(limit, POW10_U32[log + 1] - 1);
+        }
+        const BIG: u64 = for_u64::saturating_pow10(u64::MAX_LOG10);
+        assert_eq!(BIG, 10_000_000_000_000_000_000);
+    }
+
+    #[test]
+    fn test_power_of_ten() {
+        assert!(!0u8.is_power_of_ten());
+        assert!(1u8.is_power_of_ten());
+        assert!(100u8.is_power_of_ten());
+        assert!(!u8::MAX.is_power_of_ten());
+        assert_eq!(0u32.next_power_of_ten(), 1);
+        assert_eq!(1u32.next_power_of_ten(), 1);
+        assert_eq!(u32::MAX.checked_next_power_of_ten(), None);
+        assert_eq!(u128::MAX.checked_next_power_of_ten(), None);
+        assert_eq!(u64::MAX.prev_power_of_ten(), 10_000_000_000_000_000_000);
+        for x in (0..=20_000u128).chain([u64::MAX as u128, u128::MAX - 1, u128::MAX]) {
+            let next = reference::next_power_of_ten(x);
+            assert_eq!(x.is_power_of_ten(), reference::is_power_of_ten(x), "{}", x);
+            assert_eq!(x.checked_next_power_of_ten(), next, "{}", x);
+            if x <= u16::MAX as u128 {
+                let narrow = x as u16;
+                assert_eq!(narrow.is_power_of_ten(), reference::is_power_of_ten(x), "{}", x);
+                assert_eq!(
+                    narrow.checked_next_power_of_ten().map(u128::from),
+                    next.filter(|&p| p <= u16::MAX as u128),
+                    "{}",
+                    x
+                );
+            }
+            if x != 0 {
+                assert_eq!(x.prev_power_of_ten(), reference::prev_power_of_ten(x), "{}", x);
+            }
+        }
+        const NEXT: usize = for_usize::next_power_of_ten(999);
+        assert_eq!(NEXT, 1_000);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn test_next_power_of_ten_overflow() {
+        u8::MAX.next_power_of_ten();
+    }
+
+    #[test]
+    fn test_with_pow() {
+        assert_eq!(1_234u16.log10_floor_with_pow(), (3, 1_000));
+        assert_eq!(1_234u16.log10_floor_rem(), (3, 234));
+        assert_eq!(u8::MAX.log10_floor_rem(), (2, 155));
+        assert_eq!(i8::MIN.log10_floor_with_pow(), (2, -100));
+        assert_eq!(i8::MIN.log10_floor_rem(), (2, -28));
+        assert_eq!((-1_234i16).log10_floor_with_pow(), (3, -1_000));
+        assert_eq!((-1_234i16).log10_floor_rem(), (3, -234));
+        assert_eq!((-1_000i32).log10_floor_rem(), (3, 0));
+        assert_eq!(i64::MAX.log10_floor_with_pow(), (18, 1_000_000_000_000_000_000));
+        assert_eq!(u128::MAX.log10_floor_with_pow(), reference::log10_floor_with_pow(u128::MAX));
+        for x in (1..=100_000u32).chain([u32::MAX - 1, u32::MAX]) {
+            let wide = reference::log10_floor_with_pow(x as u128);
+            assert_eq!(x.log10_floor_with_pow(), (wide.0, wide.1 as u32), "{}", x);
+            let wide = reference::log10_floor_rem(x as u128);
+            assert_eq!(x.log10_floor_rem(), (wide.0, wide.1 as u32), "{}", x);
+            assert_eq!((x as u64).log10_floor_rem(), (wide.0, wide.1 as u64), "{}", x);
+        }
+        const SPLIT: (u32, u64) = for_u64::log10_floor_rem(9_876);
+        assert_eq!(SPLIT, (3, 8_876));
+    }
+
+    #[test]
+    fn test_div_pow10() {
+        assert_eq!(123_456u32.div_pow10(2), 1_234);
+        assert_eq!(123_456u32.rem_pow10(2), 56);
+        assert_eq!(123_456u32.divrem_pow10(0), (123_456, 0));
+        assert_eq!(u8::MAX.divrem_pow10(3), (0, u8::MAX));
+        assert_eq!(u128::MAX.divrem_pow10(200), (0, u128::MAX));
+        assert_eq!(u128::MAX.div_pow10(38), 3);
+        for k in 0..=40 {
+            let mut pow = Some(1u128);
+            while let Some(x) = pow {
+                for x in [x - 1, x, x + 1, u128::MAX - x] {
+                    let (quotient, rem) = reference::divrem_pow10(x, k);
+                    assert_eq!(x.divrem_pow10(k), (quotient, rem), "{} {}", x, k);
+                    let narrow = x as u64;
+                    let wide = reference::divrem_pow10(narrow as u128, k);
+                    assert_eq!(narrow.div_pow10(k) as u128, wide.0, "{} {}", narrow, k);
+                    assert_eq!(narrow.rem_pow10(k) as u128, wide.1, "{} {}", narrow, k);
+                    let narrow = x as u32;
+                    let wide = reference::divrem_pow10(narrow as u128, k);
+                    assert_eq!(narrow.divrem_pow10(k), (wide.0 as u32, wide.1 as u32));
+                    let narrow = x as usize;
+                    let expected = 10usize.checked_pow(k).map_or(0, |pow| narrow / pow);
+                    assert_eq!(narrow.div_pow10(k), expected, "{} {}", narrow, k);
+                }
+                pow = x.checked_mul(3);
+            }
+        }
+        const CUT: (u64, u64) = for_u64::divrem_pow10(9_876_543, 3);
+        assert_eq!(CUT, (9_876, 543));
+    }
+
+    #[test]
+    fn test_trailing_zeros() {
+        assert_eq!(1_200u32.strip_decimal_zeros(), (12, 2));
+        assert_eq!((-1_200i32).strip_decimal_zeros(), (-12, 2));
+        assert_eq!(0u64.strip_decimal_zeros(), (0, 0));
+        assert_eq!(0i8.decimal_trailing_zeros(), 0);
+        assert_eq!(100u8.decimal_trailing_zeros(), 2);
+        assert_eq!(i8::MIN.strip_decimal_zeros(), (i8::MIN, 0));
+        assert_eq!((-100i8).strip_decimal_zeros(), (-1, 2));
+        assert_eq!(u64::MAX.decimal_trailing_zeros(), 0);
+        assert_eq!(10_000_000_000_000_000_000u64.strip_decimal_zeros(), (1, 19));
+        assert_eq!(POW10_U128[38].strip_decimal_zeros(), (1, 38));
+        assert_eq!(i128::MIN.strip_decimal_zeros(), (i128::MIN, 0));
+        for x in (0..=20_000u128).chain([u64::MAX as u128, u128::MAX]) {
+            assert_eq!(x.strip_decimal_zeros(), reference::strip_decimal_zeros(x), "{}", x);
+            assert_eq!((x as u16).strip_decimal_zeros(), {
+                let (mantissa, zeros) = reference::strip_decimal_zeros(x as u16 as u128);
+                (mantissa as u16, zeros)
+            });
+        }
+        for pow in POW10_U128 {
+            for digits in [1u128, 7, 12, 250, 999_999, 1_000_001, 18_446_744_073_709_551_615] {
+                let x = match pow.checked_mul(digits) {
+                    Some(x) => x,
+                    None => continue,
+                };
+                let expected = reference::strip_decimal_zeros(x);
+                assert_eq!(x.strip_decimal_zeros(), expected, "{}", x);
+                if x <= u64::MAX as u128 {
+                    let (mantissa, zeros) = (x as u64).strip_decimal_zeros();
+                    assert_eq!((mantissa as u128, zeros), expected, "{}", x);
+                    assert_eq!((x as usize).decimal_trailing_zeros(), expected.1, "{}", x);
+                }
+                if x <= i32::MAX as u128 {
+                    let (mantissa, zeros) = (-(x as i32)).strip_decimal_zeros();
+                    assert_eq!((mantissa, zeros), (-(expected.0 as i32), expected.1), "{}", x);
+                }
+            }
+        }
+        const STRIPPED: (u64, u32) = for_u64::strip_decimal_zeros(1_234_000);
+        assert_eq!(STRIPPED, (1_234, 3));
+    }
+
+    #[test]
+    fn test_float() {
+        assert_eq!(1e23f64.log10_floor(), 22);
+        assert_eq!(f64::from_bits(1e23f64.to_bits() + 1).log10_floor(), 23);
+        assert_eq!(1.0f64.log10_floor(), 0);
+        assert_eq!(0.5f64.log10_floor(), -1);
+        assert_eq!((-1_000.0f64).log10_floor(), 3);
+        assert_eq!(f64::MAX.log10_floor(), 308);
+        assert_eq!(f64::MIN_POSITIVE.log10_floor(), -308);
+        assert_eq!(f64::from_bits(1).log10_floor(), -324);
+        assert_eq!(f32::MAX.log10_floor(), 38);
+        assert_eq!(f32::from_bits(1).log10_floor(), -45);
+        assert_eq!(0.1f32.log10_floor(), -1);
+        assert_eq!((<f64 as FloatLog10>::MIN_LOG10, <f64 as FloatLog10>::MAX_LOG10), (-324, 308));
+        assert_eq!((f32::MIN_LOG10, f32::MAX_LOG10), (-45, 38));
+        for x in [0.0f64, -0.0, f64::INFINITY, f64::NEG_INFINITY, f64::NAN] {
+            assert_eq!(x.checked_log10_floor(), None);
+            assert_eq!((x as f32).checked_log10_floor(), None);
+        }
+        for k in -330..=310 {
+            let pow: f64 = std::format!("1e{}", k).parse().unwrap();
+            for bits in pow.to_bits().saturating_sub(2)..=pow.to_bits() + 2 {
+                let x = f64::from_bits(bits);
+                if x != 0.0 && x.is_finite() {
+                    assert_eq!(x.log10_floor(), reference::float_log10_floor(x), "{:e}", x);
+                    assert_eq!((-x).log10_floor(), reference::float_log10_floor(x), "{:e}", x);
+                }
+            }
+            let pow: f32 = std::format!("1e{}", k).parse().unwrap();
+            for bits in pow.to_bits().saturating_sub(2)..=pow.to_bits() + 2 {
+                let x = f32::from_bits(bits);
+                if x != 0.0 && x.is_finite() {
+                    assert_eq!(x.log10_floor(), reference::float_log10_floor(x as f64), "{:e}", x);
+                }
+            }
+        }
+        const TINY: i32 = for_f64::log10_floor(5e-324);
+        assert_eq!(TINY, -324);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_float0() {
+        0.0f64.log10_floor();
+    }
+
+    #[test]
+    fn test_float_printing() {
+        assert_eq!(floor_log10_pow2(0), 0);
+        assert_eq!(floor_log10_pow2(-1), -1);
+        assert_eq!(floor_log10_pow2(10), 3);
+        assert_eq!(floor_log10_pow2(1023), 307);
+        assert_eq!(floor_log10_pow2(-1074), -324);
+        assert_eq!(floor_log10_pow5(1), 0);
+        assert_eq!(floor_log10_pow5(-1), -1);
+        assert_eq!(floor_log10_pow5(3), 2);
+        assert_eq!(floor_log10_pow5(-3), -3);
+        assert_eq!(floor_log2_pow10(1), 3);
+        assert_eq!(floor_log2_pow10(-1), -4);
+        assert_eq!(floor_log2_pow10(308), 1023);
+        assert_eq!(floor_log2_pow10(-324), -1077);
+        // And the integer ones, as far as they go.
+        for e in 0..128 {
+            let guess = LOG10S_FOR_LOG2S[e as usize];
+            assert_eq!(floor_log10_pow2(e), guess as i32, "{}", e);
+        }
+        for e in 0..=38 {
+            let log = reference::ilog_floor(2, 10u128.pow(e as u32));
+            assert_eq!(floor_log2_pow10(e), log as i32, "{}", e);
+        }
+        const LOG: i32 = floor_log10_pow5(-2620);
+        assert_eq!(LOG, -1832);
+        // And the ends of the ranges against the reference; tests/exhaustive.rs
+        // has the rest.
+        for e in [-2620, 2620] {
+            assert_eq!(floor_log10_pow2(e), reference::floor_log10_pow2(e), "{}", e);
+            assert_eq!(floor_log10_pow5(e), reference::floor_log10_pow5(e), "{}", e);
+        }
+        for e in [-1233, 1233] {
+            assert_eq!(floor_log2_pow10(e), reference::floor_log2_pow10(e), "{}", e);
+        }
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn test_float_printing_range() {
+        floor_log2_pow10(1234);
+    }
+
+}
+
+// Quick comparisons for trying out ideas; `cargo +nightly bench --features nightly`.
+#[cfg(all(test, feature = "nightly"))]
+mod benches {
+    extern crate test;
+
+    use super::*;
+    use test::{black_box, Bencher};
+
+    // Spread over all the widths of u32 rather than just the small numbers.
+    fn inputs() -> impl Iterator<Item = u32> {
+        (0..1000u32).map(|i| (i.wrapping_mul(2_654_435_761) >> (i % 32)) | 1)
+    }
+
+    #[bench]
+    fn bench_log10_floor_u32(b: &mut Bencher) {
+        b.iter(|| inputs().map(|x| black_box(x).log10_floor()).sum::<u32>());
+    }
+
+    #[bench]
+    fn bench_log10_u32(b: &mut Bencher) {
+        b.iter(|| inputs().map(|x| log10_u32(black_box(x))).sum::<u32>());
+    }
 }
@@ -0,0 +1,314 @@
+// Slow but obviously correct versions of the functions in this crate, for
+// checking the fast ones against, here or in other crates' tests.
+// They don't use any of the tables, just division (or, for rounding,
+// multiplication), and they take u128 so that one version covers every width;
+// for a signed x, pass x.unsigned_abs().
+// Like the fast versions, they panic for 0, except for decimal_digits.
+// The float ones, at the end, work the same way with bigger numbers:
+// float_log10_floor takes an f64, and the float-printing logarithms take an
+// exponent.
+
+// floor(log10(x)): how many times x can be divided by 10 before it's a single digit.
+pub const fn log10_floor(x: u128) -> u32 {
+    ilog_floor(10, x)
+}
+
+// ceil(log10(x)): the floor, plus one unless x is exactly a power of ten.
+pub const fn log10_ceil(x: u128) -> u32 {
+    let log = log10_floor(x);
+    if is_pow(10, log, x) {
+        log
+    } else {
+        log + 1
+    }
+}
+
+// log10(x) rounded on the log scale: the floor k, plus one if x >= 10^(k + 0.5),
+// i.e. if x^2 >= 10^(2k + 1).  Those don't fit in a u128, so they're compared
+// as 256-bit numbers, in eight u32 digits, least significant first.
+pub const fn log10_round(x: u128) -> u32 {
+    let log = log10_floor(x);
+    let mut square = [0u32; 8];
+    let mut i = 0;
+    while i < 4 {
+        let mut j = 0;
+        while j < 4 {
+            let product = (x >> (32 * i)) as u32 as u64 * ((x >> (32 * j)) as u32 as u64);
+            add_at(&mut square, i + j, product);
+            j += 1;
+        }
+        i += 1;
+    }
+    let mut pow = [0u32; 8];
+    pow[0] = 1;
+    let mut k = 0;
+    while k < 2 * log + 1 {
+        let mut carry = 0u64;
+        let mut i = 0;
+        while i < 8 {
+            let digit = pow[i] as u64 * 10 + carry;
+            pow[i] = digit as u32;
+            carry = digit >> 32;
+            i += 1;
+        }
+        k += 1;
+    }
+    let mut i = 8;
+    while i > 0 {
+        i -= 1;
+        if square[i] != pow[i] {
+            return if square[i] > pow[i] { log + 1 } else { log };
+        }
+    }
+    log + 1 // x^2 == 10^(2k + 1) can't happen, but it would round up
+}
+
+// Adds value to the 256-bit number in digits, starting at digit `at`.
+const fn add_at(digits: &mut [u32; 8], mut at: usize, mut value: u64) {
+    while value != 0 {
+        let sum = digits[at] as u64 + (value as u32 as u64);
+        digits[at] = sum as u32;
+        value = (value >> 32) + (sum >> 32);
+        at += 1;
+    }
+}
+
+// floor(log10(x)) with 10 to that power, and with x minus that power.
+pub const fn log10_floor_with_pow(x: u128) -> (u32, u128) {
+    let log = log10_floor(x);
+    (log, prev_power_of_ten(x))
+}
+
+pub const fn log10_floor_rem(x: u128) -> (u32, u128) {
+    let (log, pow) = log10_floor_with_pow(x);
+    (log, x - pow)
+}
+
+// The number of decimal digits in x: one, plus one for every time x can be
+// divided by 10 before it's a single digit.  0 has one digit.
+pub const fn decimal_digits(mut x: u128) -> u32 {
+    let mut digits = 1;
+    while x >= 10 {
+        x /= 10;
+        digits += 1;
+    }
+    digits
+}
+
+// The length of x as Display prints it: its digits, plus one for the '-' if
+// it's negative.  x is the magnitude, as everywhere else here.
+pub const fn display_len(x: u128, negative: bool) -> u32 {
+    decimal_digits(x) + negative as u32
+}
+
+// x with its decimal trailing zeros taken off, and how many there were: x
+// divided by 10 for as long as nothing is left over.  0 gives (0, 0).
+pub const fn strip_decimal_zeros(mut x: u128) -> (u128, u32) {
+    let mut zeros = 0;
+    while x != 0 && x % 10 == 0 {
+        x /= 10;
+        zeros += 1;
+    }
+    (x, zeros)
+}
+
+pub const fn decimal_trailing_zeros(x: u128) -> u32 {
+    strip_decimal_zeros(x).1
+}
+
+// 10^k, or None if it doesn't fit in a u128: 1 multiplied by 10 k times.
+pub const fn checked_pow10(k: u32) -> Option<u128> {
+    let mut pow: u128 = 1;
+    let mut i = 0;
+    while i < k {
+        pow = match pow.checked_mul(10) {
+            Some(pow) => pow,
+            None => return None,
+        };
+        i += 1;
+    }
+    Some(pow)
+}
+
+// x / 10^k and x % 10^k, by dividing x by 10 k times, keeping what falls off
+// the end; past 10^38 there's nothing left of x.
+pub const fn divrem_pow10(x: u128, k: u32) -> (u128, u128) {
+    let mut quotient = x;
+    let mut i = 0;
+    while i < k && quotient != 0 {
+        quotient /= 10;
+        i += 1;
+    }
+    let rem = match checked_pow10(k) {
+        Some(pow) => x - quotient * pow,
+        None => x,
+    };
+    (quotient, rem)
+}
+
+// Is x 10^k for some k?  0 isn't.
+pub const fn is_power_of_ten(x: u128) -> bool {
+    x != 0 && is_pow(10, log10_floor(x), x)
+}
+
+// The smallest power of ten >= x, or None if it doesn't fit: 1 multiplied by
+// 10 until it's big enough.
+pub const fn next_power_of_ten(x: u128) -> Option<u128> {
+    let mut pow: u128 = 1;
+    while pow < x {
+        pow = match pow.checked_mul(10) {
+            Some(pow) => pow,
+            None => return None,
+        };
+    }
+    Some(pow)
+}
+
+// The largest power of ten <= x, which panics for 0.
+pub const fn prev_power_of_ten(x: u128) -> u128 {
+    match checked_pow10(log10_floor(x)) {
+        Some(pow) => pow,
+        None => unreachable!(),
+    }
+}
+
+// floor(log_base(x)): how many times x can be divided by base before it's
+// less than base.
+pub const fn ilog_floor(base: u128, mut x: u128) -> u32 {
+    assert!(base >= 2, "the base of a logarithm must be at least 2");
+    assert!(x != 0, "logarithm of 0");
+    let mut log = 0;
+    while x >= base {
+        x /= base;
+        log += 1;
+    }
+    log
+}
+
+// Is x == base^exp?  Divides x by base exp times, checking nothing is lost.
+const fn is_pow(base: u128, exp: u32, mut x: u128) -> bool {
+    let mut i = 0;
+    while i < exp {
+        if x % base != 0 {
+            return false;
+        }
+        x /= base;
+        i += 1;
+    }
+    x == 1
+}
+
+// floor(log10(|x|)) for a float, which panics for 0, infinity and NaN; for an
+// f32, pass x as f64, which is exact.  x is m * 2^e for integers m and e, so
+// it's num / den for integers num and den, one of them a power of two.  Then
+// den is multiplied by 10 for as long as it stays <= num, or num is multiplied
+// by 10 until it's at least den.  Neither gets past 2^1078, so they're
+// 1,280-bit numbers, in forty u32 digits, least significant first.
+pub const fn float_log10_floor(x: f64) -> i32 {
+    assert!(x != 0.0 && x.is_finite(), "logarithm of 0, infinity or NaN");
+    let bits = x.to_bits() & !(1 << 63);
+    let field = (bits >> 52) as i32;
+    let (m, e) = if field == 0 {
+        (bits, -1074)
+    } else {
+        (bits & ((1 << 52) - 1) | 1 << 52, field - 1075)
+    };
+    let mut num = [0u32; 40];
+    num[0] = m as u32;
+    num[1] = (m >> 32) as u32;
+    let mut den = [0u32; 40];
+    den[0] = 1;
+    let mut shift = e.unsigned_abs();
+    while shift > 0 {
+        let step = if shift < 31 { shift } else { 31 };
+        if e > 0 {
+            mul_small(&mut num, 1 << step);
+        } else {
+            mul_small(&mut den, 1 << step);
+        }
+        shift -= step;
+    }
+    log_floor_ratio(10, num, den)
+}
+
+// floor(log10(2^e)), floor(log10(5^e)) and floor(log2(10^e)), for any e whose
+// power fits comfortably in 6,400 bits, which covers the ranges of the fast
+// versions.  For negative e the power is 1 / base^-e, so like a float it's
+// num / den with base^|e| on one side and 1 on the other.
+
+pub const fn floor_log10_pow2(e: i32) -> i32 {
+    pow_log_floor(10, 2, e)
+}
+
+pub const fn floor_log10_pow5(e: i32) -> i32 {
+    pow_log_floor(10, 5, e)
+}
+
+pub const fn floor_log2_pow10(e: i32) -> i32 {
+    pow_log_floor(2, 10, e)
+}
+
+// The floor of the log to log_base of base^exp, with base^|exp| worked out
+// one multiplication at a time, in two hundred u32 digits.
+const fn pow_log_floor(log_base: u32, base: u32, exp: i32) -> i32 {
+    let mut pow = [0u32; 200];
+    pow[0] = 1;
+    let mut one = [0u32; 200];
+    one[0] = 1;
+    let mut i = 0;
+    while i < exp.unsigned_abs() {
+        mul_small(&mut pow, base);
+        i += 1;
+    }
+    if exp < 0 {
+        log_floor_ratio(log_base, one, pow)
+    } else {
+        log_floor_ratio(log_base, pow, one)
+    }
+}
+
+// floor(log_base(num / den)) for nonzero num and den: den is multiplied by base
+// for as long as it stays <= num, or num is multiplied by base until it's at
+// least den.
+const fn log_floor_ratio<const N: usize>(base: u32, mut num: [u32; N], mut den: [u32; N]) -> i32 {
+    let mut log = 0;
+    if less(&num, &den) {
+        while less(&num, &den) {
+            mul_small(&mut num, base);
+            log -= 1;
+        }
+    } else {
+        mul_small(&mut den, base);
+        while !less(&num, &den) {
+            mul_small(&mut den, base);
+            log += 1;
+        }
+    }
+    log
+}
+
+// Multiplies the big number in digits, least significant first, by factor,
+// which panics if the product doesn't fit.
+const fn mul_small<const N: usize>(digits: &mut [u32; N], factor: u32) {
+    let mut carry = 0u64;
+    let mut i = 0;
+    while i < N {
+        let digit = digits[i] as u64 * factor as u64 + carry;
+        digits[i] = digit as u32;
+        carry = digit >> 32;
+        i += 1;
+    }
+    assert!(carry == 0);
+}
+
+// Is a < b, as big numbers?
+const fn less<const N: usize>(a: &[u32; N], b: &[u32; N]) -> bool {
+    let mut i = N;
+    while i > 0 {
+        i -= 1;
+        if a[i] != b[i] {
+            return a[i] < b[i];
+        }
+    }
+    false
+}
@@ -0,0 +1,174 @@
+// The different ways of computing floor(log10(x)) that this crate has,
+// behind one trait so they can be swapped for each other, e.g. with
+// x.log10_floor_with::<Linear>(), and compared.  They all give the same
+// answers; which is fastest depends on the target and the inputs.
+
+use super::*;
+
+// x must not be 0 for any of these; what they do with 0 differs.
+// IntLog10::log10_floor_with checks for it before getting here.
+pub trait Strategy {
+    fn log10_u8(x: u8) -> u32;
+    fn log10_u16(x: u16) -> u32;
+    fn log10_u32(x: u32) -> u32;
+    fn log10_u64(x: u64) -> u32;
+    fn log10_u128(x: u128) -> u32;
+}
+
+// Guess from LOG10S_FOR_LOG2S, then correct against LIMITS.
+// This is what log10_floor uses.
+pub struct TwoTable;
+
+impl Strategy for TwoTable {
+    #[inline]
+    fn log10_u8(x: u8) -> u32 {
+        for_u8::log10_floor(x)
+    }
+
+    #[inline]
+    fn log10_u16(x: u16) -> u32 {
+        for_u16::log10_floor(x)
+    }
+
+    #[inline]
+    fn log10_u32(x: u32) -> u32 {
+        for_u32::log10_floor(x)
+    }
+
+    #[inline]
+    fn log10_u64(x: u64) -> u32 {
+        for_u64::log10_floor(x)
+    }
+
+    #[inline]
+    fn log10_u128(x: u128) -> u32 {
+        for_u128::log10_floor(x)
+    }
+}
+
+// jhpratt's single table, where the carry out of an addition is the correction.
+// The narrow types just widen to u32.
+pub struct CarryTable;
+
+impl Strategy for CarryTable {
+    #[inline]
+    fn log10_u8(x: u8) -> u32 {
+        super::log10_u32(x as u32)
+    }
+
+    #[inline]
+    fn log10_u16(x: u16) -> u32 {
+        super::log10_u32(x as u32)
+    }
+
+    #[inline]
+    fn log10_u32(x: u32) -> u32 {
+        super::log10_u32(x)
+    }
+
+    #[inline]
+    fn log10_u64(x: u64) -> u32 {
+        super::log10_u64(x)
+    }
+
+    #[inline]
+    fn log10_u128(x: u128) -> u32 {
+        super::log10_u128(x)
+    }
+}
+
+// No log2 at all: just count the LIMITS below x, by binary search.
+// The entries at the end of LIMITS that are just the type's MAX are never
+// below x, so it doesn't matter that they aren't really limits.
+pub struct BinarySearch;
+
+// The same, by comparing against each of LIMITS in turn.  That's the fastest
+// of all when nearly every x is small.
+pub struct Linear;
+
+macro_rules! compare_chains {
+    ($t:ident, $limits:ident, $binary:ident, $linear:ident) => {
+        const fn $binary(x: $t) -> u32 {
+            let mut low = 0;
+            let mut len = $limits.len();
+            while len > 0 {
+                let half = len / 2;
+                if x > $limits[low + half] {
+                    low += half + 1;
+                    len -= half + 1;
+                } else {
+                    len = half;
+                }
+            }
+            low as u32
+        }
+
+        const fn $linear(x: $t) -> u32 {
+            let mut log = 0;
+            while log < $limits.len() && x > $limits[log] {
+                log += 1;
+            }
+            log as u32
+        }
+    };
+}
+
+compare_chains!(u8, LIMITS_U8, binary_u8, linear_u8);
+compare_chains!(u16, LIMITS_U16, binary_u16, linear_u16);
+compare_chains!(u32, LIMITS_U32, binary_u32, linear_u32);
+compare_chains!(u64, LIMITS_U64, binary_u64, linear_u64);
+compare_chains!(u128, LIMITS_U128, binary_u128, linear_u128);
+
+impl Strategy for BinarySearch {
+    #[inline]
+    fn log10_u8(x: u8) -> u32 {
+        binary_u8(x)
+    }
+
+    #[inline]
+    fn log10_u16(x: u16) -> u32 {
+        binary_u16(x)
+    }
+
+    #[inline]
+    fn log10_u32(x: u32) -> u32 {
+        binary_u32(x)
+    }
+
+    #[inline]
+    fn log10_u64(x: u64) -> u32 {
+        binary_u64(x)
+    }
+
+    #[inline]
+    fn log10_u128(x: u128) -> u32 {
+        binary_u128(x)
+    }
+}
+
+impl Strategy for Linear {
+    #[inline]
+    fn log10_u8(x: u8) -> u32 {
+        linear_u8(x)
+    }
+
+    #[inline]
+    fn log10_u16(x: u16) -> u32 {
+        linear_u16(x)
+    }
+
+    #[inline]
+    fn log10_u32(x: u32) -> u32 {
+        linear_u32(x)
+    }
+
+    #[inline]
+    fn log10_u64(x: u64) -> u32 {
+        linear_u64(x)
+    }
+
+    #[inline]
+    fn log10_u128(x: u128) -> u32 {
+        linear_u128(x)
+    }
+}
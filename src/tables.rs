@@ -0,0 +1,831 @@
+// Compile-time generators for the guess, limit, and midpoint tables, plus
+// checks that each entry matches its definition, worked out a different way.
+// The tables get built and checked in u128 (or 256 bits, as a (hi, lo) pair,
+// where squares are involved) and are narrowed to each width with narrow!.
+
+// make_guesses(base)[n] is floor(log_base(2^n)), for n in 0..128.
+pub(crate) const fn make_guesses(base: u128) -> [u8; 128] {
+    assert!(base >= 2, "the base of a logarithm must be at least 2");
+    let mut guesses = [0; 128];
+    let mut log = 0;
+    let mut pow: u128 = 1; // base^log
+    let mut n = 0;
+    while n < 128 {
+        // Advance log while base^(log + 1) <= 2^n.
+        while let Some(next) = pow.checked_mul(base) {
+            if next > 1 << n {
+                break;
+            }
+            pow = next;
+            log += 1;
+        }
+        guesses[n] = log;
+        n += 1;
+    }
+    guesses
+}
+
+// make_limits(base, max)[log] is the highest x for which floor(log_base(x)) == log,
+// i.e. base^(log + 1) - 1, or max once that is more than max.
+pub(crate) const fn make_limits<const N: usize>(base: u128, max: u128) -> [u128; N] {
+    let mut limits = [max; N];
+    let mut pow = base; // base^(log + 1)
+    let mut log = 0;
+    while log < N && pow - 1 <= max {
+        limits[log] = pow - 1;
+        match pow.checked_mul(base) {
+            Some(next) => pow = next,
+            None => break,
+        }
+        log += 1;
+    }
+    limits
+}
+
+// make_pows(base)[k] is base^k.  Every entry has to fit in a u128.
+pub(crate) const fn make_pows<const N: usize>(base: u128) -> [u128; N] {
+    let mut pows = [1u128; N];
+    let mut k = 1;
+    while k < N {
+        pows[k] = match pows[k - 1].checked_mul(base) {
+            Some(pow) => pow,
+            None => panic!("power doesn't fit in a u128"),
+        };
+        k += 1;
+    }
+    pows
+}
+
+// The limits for a width, from its powers: limits[log] is pows[log + 1] - 1,
+// or max once there is no next power.  This is the same as make_limits, but it
+// lets the limits be built from the very table that is exported as powers.
+pub(crate) const fn limits_from_pows<const N: usize>(pows: &[u128], max: u128) -> [u128; N] {
+    let mut limits = [max; N];
+    let mut log = 0;
+    while log < N && log + 1 < pows.len() {
+        limits[log] = pows[log + 1] - 1;
+        log += 1;
+    }
+    limits
+}
+
+// make_mids(base, max)[log] is the highest x below the geometric midpoint
+// base^(log + 0.5), i.e. the highest x with x^2 < base^(2*log + 1), or max
+// once that is more than max.
+pub(crate) const fn make_mids<const N: usize>(base: u128, max: u128) -> [u128; N] {
+    let mut mids = [max; N];
+    let mut pow = Some((0, base)); // base^(2*log + 1)
+    let mut log = 0;
+    while log < N {
+        let (hi, lo) = match pow {
+            Some(pow) => pow,
+            None => break,
+        };
+        // Binary search for the highest x <= max with x^2 < pow.
+        let (mut low, mut high) = (0, max);
+        while low < high {
+            let x = low + (high - low) / 2 + 1;
+            if lt_wide(mul_wide(x, x), (hi, lo)) {
+                low = x;
+            } else {
+                high = x - 1;
+            }
+        }
+        mids[log] = low;
+        pow = match mul_wide_small((hi, lo), base) {
+            Some(pow) => mul_wide_small(pow, base),
+            None => None,
+        };
+        log += 1;
+    }
+    mids
+}
+
+// Does every guess match its definition?  Each one is checked against
+// base^guess <= 2^n < base^(guess + 1) using checked_pow rather than the
+// running product make_guesses uses.
+pub(crate) const fn guesses_ok(base: u128, guesses: &[u8; 128]) -> bool {
+    let mut n = 0;
+    while n < 128 {
+        let guess = guesses[n] as u32;
+        let low_ok = match base.checked_pow(guess) {
+            Some(pow) => pow <= 1 << n,
+            None => false,
+        };
+        let high_ok = match base.checked_pow(guess + 1) {
+            Some(pow) => pow > 1 << n,
+            None => true,
+        };
+        if !low_ok || !high_ok {
+            return false;
+        }
+        n += 1;
+    }
+    true
+}
+
+// Does every limit match its definition, and is there one for every guess
+// the width can produce?
+pub(crate) const fn limits_ok(
+    base: u128,
+    bits: u32,
+    guesses: &[u8; 128],
+    limits: &[u128],
+    max: u128,
+) -> bool {
+    if guesses[bits as usize - 1] as usize >= limits.len() {
+        return false;
+    }
+    let mut log = 0;
+    while log < limits.len() {
+        let expected = match base.checked_pow(log as u32 + 1) {
+            Some(pow) if pow - 1 <= max => pow - 1,
+            _ => max,
+        };
+        if limits[log] != expected {
+            return false;
+        }
+        log += 1;
+    }
+    true
+}
+
+// Is every power right, using checked_pow rather than a running product,
+// and is there one for every power up to max and none past it?
+pub(crate) const fn pows_ok(base: u128, pows: &[u128], max: u128) -> bool {
+    let mut k = 0;
+    while k < pows.len() {
+        match base.checked_pow(k as u32) {
+            Some(pow) if pow == pows[k] && pow <= max => {}
+            _ => return false,
+        }
+        k += 1;
+    }
+    match base.checked_pow(k as u32) {
+        Some(pow) => pow > max,
+        None => true,
+    }
+}
+
+// make_div_shifts(bits)[k], for k >= 1, is (bits - k) + ceil(log2(5^k)), and
+// make_div_magics(bits)[k] is ceil(2^shift / 5^k) for that shift.  Then for any
+// bits-wide x, x / 10^k is ((x >> k) * magic) >> shift: x >> k is x / 2^k, which
+// leaves bits - k bits to divide by 5^k, and a multiplier that is rounded up by
+// less than 5^k <= 2^ceil(log2(5^k)) is exact for every numerator that wide.
+// The shift is always at least bits, so only the high half of the product is
+// needed.  Entry 0 is unused, since dividing by 1 needs no magic.
+pub(crate) const fn make_div_shifts<const N: usize>(bits: u32) -> [u8; N] {
+    let mut shifts = [0; N];
+    let mut pow: u128 = 5; // 5^k
+    let mut k = 1;
+    while k < N {
+        // 5^k isn't a power of two, so its ceil(log2) is one more than its floor.
+        shifts[k] = (bits - k as u32 + 128 - pow.leading_zeros()) as u8;
+        pow *= 5;
+        k += 1;
+    }
+    shifts
+}
+
+pub(crate) const fn make_div_magics<const N: usize>(bits: u32) -> [u128; N] {
+    let shifts: [u8; N] = make_div_shifts(bits);
+    let mut magics = [0; N];
+    let mut pow: u128 = 5; // 5^k
+    let mut k = 1;
+    while k < N {
+        // Long division of 2^shift by 5^k, a bit at a time, since 2^shift is
+        // usually too big for a u128.  The quotient fits; see div_magics_ok.
+        let mut quotient: u128 = 0;
+        let mut rem: u128 = 0;
+        let mut bit = shifts[k] as i32;
+        while bit >= 0 {
+            rem = (rem << 1) | (bit == shifts[k] as i32) as u128;
+            quotient <<= 1;
+            if rem >= pow {
+                rem -= pow;
+                quotient |= 1;
+            }
+            bit -= 1;
+        }
+        magics[k] = if rem == 0 { quotient } else { quotient + 1 };
+        pow *= 5;
+        k += 1;
+    }
+    magics
+}
+
+// Does every magic number divide by 10^k?  Each one is checked by multiplying
+// it back out: 2^shift <= magic * 5^k <= 2^shift + 2^ceil(log2(5^k)), where
+// 5^k <= 2^ceil(log2(5^k)) < 2 * 5^k, with the shift at least bits.
+pub(crate) const fn div_magics_ok(bits: u32, magics: &[u128], shifts: &[u8]) -> bool {
+    let mut k = 1;
+    while k < magics.len() {
+        let pow = match 5u128.checked_pow(k as u32) {
+            Some(pow) => pow,
+            None => return false,
+        };
+        let shift = shifts[k] as u32;
+        if shift < bits || shift + k as u32 <= bits {
+            return false;
+        }
+        let log = shift + k as u32 - bits; // ceil(log2(5^k))
+        if log >= 128 || pow > 1 << log || pow <= 1 << (log - 1) {
+            return false;
+        }
+        let product = mul_wide(magics[k], pow);
+        let low = wide_pow2(shift);
+        let high = match add_wide(low, (0, 1 << log)) {
+            Some(high) => high,
+            None => return false,
+        };
+        if lt_wide(product, low) || lt_wide(high, product) {
+            return false;
+        }
+        k += 1;
+    }
+    true
+}
+
+// make_inv5s(bits)[k] is the inverse of 5^k mod 2^bits, which exists since 5^k
+// is odd.  Multiplying a bits-wide x by it gives x / 5^k exactly when 5^k
+// divides x, and a number bigger than MAX / 5^k otherwise, which makes for a
+// divisibility test with no division.  It's found with Newton's iteration,
+// which doubles the number of correct low bits each time, starting from the
+// three that any odd number gets right as its own inverse.
+pub(crate) const fn make_inv5s<const N: usize>(bits: u32) -> [u128; N] {
+    let mask = u128::MAX >> (128 - bits);
+    let mut inv5s = [0; N];
+    let mut pow: u128 = 1; // 5^k
+    let mut k = 0;
+    while k < N {
+        let mut inv = pow;
+        let mut correct = 3;
+        while correct < bits {
+            inv = inv.wrapping_mul(2u128.wrapping_sub(pow.wrapping_mul(inv)));
+            correct *= 2;
+        }
+        inv5s[k] = inv & mask;
+        pow *= 5;
+        k += 1;
+    }
+    inv5s
+}
+
+// make_quotients(pows, max)[k] is max / pows[k], rounded down.
+pub(crate) const fn make_quotients<const N: usize>(pows: &[u128], max: u128) -> [u128; N] {
+    let mut quotients = [0; N];
+    let mut k = 0;
+    while k < N {
+        quotients[k] = max / pows[k];
+        k += 1;
+    }
+    quotients
+}
+
+// Is every inverse right, checked by multiplying it back out with 5^k?
+pub(crate) const fn inv5s_ok(bits: u32, inv5s: &[u128]) -> bool {
+    let mask = u128::MAX >> (128 - bits);
+    let mut k = 0;
+    while k < inv5s.len() {
+        match 5u128.checked_pow(k as u32) {
+            Some(pow) if inv5s[k] <= mask && inv5s[k].wrapping_mul(pow) & mask == 1 => {}
+            _ => return false,
+        }
+        k += 1;
+    }
+    true
+}
+
+// Is every quotient the highest q with q * pows[k] <= max?
+pub(crate) const fn quotients_ok(pows: &[u128], quotients: &[u128], max: u128) -> bool {
+    let mut k = 0;
+    while k < quotients.len() {
+        let low_ok = match quotients[k].checked_mul(pows[k]) {
+            Some(product) => product <= max,
+            None => false,
+        };
+        let high_ok = match quotients[k].checked_add(1) {
+            Some(next) => match next.checked_mul(pows[k]) {
+                Some(product) => product > max,
+                None => true,
+            },
+            None => true,
+        };
+        if !low_ok || !high_ok {
+            return false;
+        }
+        k += 1;
+    }
+    true
+}
+
+// Is every midpoint right?
+pub(crate) const fn mids_ok(base: u128, mids: &[u128], max: u128) -> bool {
+    let mut log = 0;
+    while log < mids.len() {
+        let mid = mids[log];
+        let pow = match wide_pow(base, 2 * log as u32 + 1) {
+            Some(pow) => pow,
+            None => (u128::MAX, u128::MAX),
+        };
+        if !lt_wide(mul_wide(mid, mid), pow) {
+            return false;
+        }
+        if mid < max && lt_wide(mul_wide(mid + 1, mid + 1), pow) {
+            return false;
+        }
+        log += 1;
+    }
+    true
+}
+
+// The number of entries a limits table needs for a width: one for every guess.
+pub(crate) const fn limits_len(guesses: &[u8; 128], bits: u32) -> usize {
+    guesses[bits as usize - 1] as usize + 1
+}
+
+// The number of different values floor(log_base(x)) can have for x in 1..=max,
+// which is how many entries a midpoint table needs.
+pub(crate) const fn logs_len(base: u128, max: u128) -> usize {
+    let mut len = 1;
+    let mut pow = base;
+    while pow <= max {
+        len += 1;
+        pow = match pow.checked_mul(base) {
+            Some(next) => next,
+            None => break,
+        };
+    }
+    len
+}
+
+// make_carries(base, bits)[n] is what to add to a bits-wide x with
+// floor(log2(x)) == n so that it carries out of the top bit exactly when
+// x >= base^(guess + 1): 2^bits - base^(guess + 1), mod 2^128, if that power
+// is at most 2^(n + 1) - 1, and otherwise 0, since nothing of that width
+// can reach it.  This is jhpratt's trick from log10_u32.
+pub(crate) const fn make_carries(base: u128, bits: u32) -> [u128; 128] {
+    let guesses = make_guesses(base);
+    let mut carries = [0; 128];
+    let mut n = 0;
+    while n < bits as usize {
+        if let Some(pow) = base.checked_pow(guesses[n] as u32 + 1) {
+            let top = if n == 127 { u128::MAX } else { (1 << (n + 1)) - 1 };
+            if pow <= top {
+                carries[n] = if bits == 128 {
+                    pow.wrapping_neg()
+                } else {
+                    (1 << bits) - pow
+                };
+            }
+        }
+        n += 1;
+    }
+    carries
+}
+
+// The full 256-bit product of a and b, as (hi, lo).
+pub(crate) const fn mul_wide(a: u128, b: u128) -> (u128, u128) {
+    const MASK: u128 = u64::MAX as u128;
+    let (a_hi, a_lo) = (a >> 64, a & MASK);
+    let (b_hi, b_lo) = (b >> 64, b & MASK);
+    let lo_lo = a_lo * b_lo;
+    let hi_lo = a_hi * b_lo;
+    let lo_hi = a_lo * b_hi;
+    let hi_hi = a_hi * b_hi;
+    let mid = (lo_lo >> 64) + (hi_lo & MASK) + (lo_hi & MASK);
+    let lo = (mid << 64) | (lo_lo & MASK);
+    let hi = hi_hi + (hi_lo >> 64) + (lo_hi >> 64) + (mid >> 64);
+    (hi, lo)
+}
+
+// (hi, lo) * b, or None if that doesn't fit in 256 bits.
+const fn mul_wide_small((hi, lo): (u128, u128), b: u128) -> Option<(u128, u128)> {
+    let (carry, lo) = mul_wide(lo, b);
+    let (over, hi) = mul_wide(hi, b);
+    if over != 0 {
+        return None;
+    }
+    match hi.checked_add(carry) {
+        Some(hi) => Some((hi, lo)),
+        None => None,
+    }
+}
+
+// base^exp in 256 bits, or None if it doesn't fit.
+const fn wide_pow(base: u128, exp: u32) -> Option<(u128, u128)> {
+    let mut pow = (0, 1);
+    let mut i = 0;
+    while i < exp {
+        pow = match mul_wide_small(pow, base) {
+            Some(pow) => pow,
+            None => return None,
+        };
+        i += 1;
+    }
+    Some(pow)
+}
+
+// 2^exp in 256 bits; exp must be below 256.
+const fn wide_pow2(exp: u32) -> (u128, u128) {
+    if exp >= 128 {
+        (1 << (exp - 128), 0)
+    } else {
+        (0, 1 << exp)
+    }
+}
+
+// a + b, or None if that doesn't fit in 256 bits.
+const fn add_wide((a_hi, a_lo): (u128, u128), (b_hi, b_lo): (u128, u128)) -> Option<(u128, u128)> {
+    let (lo, carry) = a_lo.overflowing_add(b_lo);
+    let hi = match a_hi.checked_add(b_hi) {
+        Some(hi) => hi.checked_add(carry as u128),
+        None => None,
+    };
+    match hi {
+        Some(hi) => Some((hi, lo)),
+        None => None,
+    }
+}
+
+const fn lt_wide((a_hi, a_lo): (u128, u128), (b_hi, b_lo): (u128, u128)) -> bool {
+    a_hi < b_hi || (a_hi == b_hi && a_lo < b_lo)
+}
+
+// Widens a table to u128, for the checks.
+macro_rules! widen {
+    ($table:expr) => {{
+        let mut wide = [0; $table.len()];
+        let mut i = 0;
+        while i < wide.len() {
+            wide[i] = $table[i] as u128;
+            i += 1;
+        }
+        wide
+    }};
+}
+
+// Narrows a table computed in u128 to $t, checking that nothing is lost.
+macro_rules! narrow {
+    ($t:ty, $wide:expr) => {{
+        let mut table = [0; $wide.len()];
+        let mut i = 0;
+        while i < table.len() {
+            assert!($wide[i] <= <$t>::MAX as u128);
+            table[i] = $wide[i] as $t;
+            i += 1;
+        }
+        table
+    }};
+}
+
+// The float tables need powers of five and ten out to about 2^±1077, far more
+// than fits even in 256 bits, so they're worked out with these little big
+// integers: 16 u64 limbs, least significant first.
+type Big = [u64; 16];
+
+const fn big(x: u64) -> Big {
+    let mut big = [0; 16];
+    big[0] = x;
+    big
+}
+
+// a * m; panics if that doesn't fit.
+const fn big_mul(a: &Big, m: u64) -> Big {
+    let mut product = [0; 16];
+    let mut carry: u128 = 0;
+    let mut i = 0;
+    while i < 16 {
+        let digit = a[i] as u128 * m as u128 + carry;
+        product[i] = digit as u64;
+        carry = digit >> 64;
+        i += 1;
+    }
+    assert!(carry == 0, "big integer overflow");
+    product
+}
+
+// a << shift; panics if that doesn't fit.
+const fn big_shl(a: &Big, shift: u32) -> Big {
+    assert!(big_bit_len(a) + shift <= 1024, "big integer overflow");
+    let limbs = (shift / 64) as usize;
+    let bits = shift % 64;
+    let mut shifted = [0; 16];
+    let mut i = 16;
+    while i > limbs {
+        i -= 1;
+        let from = i - limbs;
+        shifted[i] = a[from] << bits;
+        if bits > 0 && from > 0 {
+            shifted[i] |= a[from - 1] >> (64 - bits);
+        }
+    }
+    shifted
+}
+
+// Bits shift through shift + 63 of a, and whether any bit below them is set.
+const fn big_shr(a: &Big, shift: u32) -> (u64, bool) {
+    let limbs = (shift / 64) as usize;
+    let bits = shift % 64;
+    let mut low = a[limbs] >> bits;
+    if bits > 0 && limbs + 1 < 16 {
+        low |= a[limbs + 1] << (64 - bits);
+    }
+    let mut sticky = bits > 0 && a[limbs] << (64 - bits) != 0;
+    let mut i = 0;
+    while i < limbs {
+        sticky |= a[i] != 0;
+        i += 1;
+    }
+    (low, sticky)
+}
+
+// a - b, where b <= a.
+const fn big_sub(a: &Big, b: &Big) -> Big {
+    let mut diff = [0; 16];
+    let mut borrow = false;
+    let mut i = 0;
+    while i < 16 {
+        let (digit, under) = a[i].overflowing_sub(b[i]);
+        let (digit, under_again) = digit.overflowing_sub(borrow as u64);
+        diff[i] = digit;
+        borrow = under || under_again;
+        i += 1;
+    }
+    diff
+}
+
+const fn big_lt(a: &Big, b: &Big) -> bool {
+    let mut i = 16;
+    while i > 0 {
+        i -= 1;
+        if a[i] != b[i] {
+            return a[i] < b[i];
+        }
+    }
+    false
+}
+
+const fn big_bit_len(a: &Big) -> u32 {
+    let mut i = 16;
+    while i > 0 {
+        i -= 1;
+        if a[i] != 0 {
+            return 64 * i as u32 + 64 - a[i].leading_zeros();
+        }
+    }
+    0
+}
+
+// POW5S[n] is 5^n, which, with a power of two, makes 10^n or 10^-n.
+// That's enough for every power of ten an f64 can get near.  It's only ever
+// used at compile time, so it being big doesn't matter.
+#[allow(clippy::large_const_arrays)]
+const POW5S: [Big; 326] = {
+    let mut pows = [big(1); 326];
+    let mut n = 1;
+    while n < pows.len() {
+        pows[n] = big_mul(&pows[n - 1], 5);
+        n += 1;
+    }
+    pows
+};
+
+// The float functions below describe a float format by p, the number of bits
+// in its significands, counting the hidden one, and e_min, where its smallest
+// subnormal is 2^e_min: 53 and -1074 for f64, 24 and -149 for f32.  They work
+// with the bits of positive floats, which are in the same order as the floats
+// themselves, and which for x = m * 2^e are
+//
+//   ((e - e_min) << (p - 1)) + m
+//
+// for a normal x, with 2^(p - 1) <= m < 2^p, and for a subnormal one, with
+// e = e_min and m < 2^(p - 1).  An m of 2^p, from rounding up, carries into
+// the exponent, and comes out as 2^(p - 1) * 2^(e + 1), as it should.
+
+// POW5_LENS[n] is the number of bits in 5^n, which is floor(log2(5^n)) + 1,
+// and for n >= 1 also ceil(log2(5^n)), since 5^n is never a power of two after
+// 5^0.  Comparisons between powers of two, five and ten only need these.
+// 5^1233 is 2,863 bits, which 48 limbs has room for.
+#[allow(clippy::large_const_arrays)]
+const POW5_LENS: [u16; 1234] = {
+    let mut lens = [0; 1234];
+    let mut pow = [0u64; 48];
+    pow[0] = 1;
+    let mut used = 1; // the limbs of pow that might not be 0
+    let mut n = 0;
+    while n < lens.len() {
+        let top = pow[used - 1];
+        lens[n] = (64 * used as u32 - top.leading_zeros()) as u16;
+        let mut carry: u128 = 0;
+        let mut i = 0;
+        while i < used {
+            let digit = pow[i] as u128 * 5 + carry;
+            pow[i] = digit as u64;
+            carry = digit >> 64;
+            i += 1;
+        }
+        if carry != 0 {
+            pow[used] = carry as u64;
+            used += 1;
+        }
+        n += 1;
+    }
+    lens
+};
+
+const fn pow5_len(n: u32) -> i32 {
+    POW5_LENS[n as usize] as i32
+}
+
+// Is 10^k <= 2^e?  That's 5^k <= 2^(e - k).
+const fn pow10_le_pow2(k: i32, e: i32) -> bool {
+    if k > 0 {
+        pow5_len(k as u32) <= e - k
+    } else {
+        // 2^(k - e) <= 5^-k
+        k - e < pow5_len(k.unsigned_abs())
+    }
+}
+
+// Is 10^k <= 5^e?  That's 2^k <= 5^(e - k).
+const fn pow10_le_pow5(k: i32, e: i32) -> bool {
+    if e >= k {
+        k < pow5_len((e - k) as u32)
+    } else {
+        // 5^(k - e) <= 2^-k
+        pow5_len((k - e) as u32) <= -k
+    }
+}
+
+// Is 2^k <= 10^e?  That's 2^(k - e) <= 5^e.
+const fn pow2_le_pow10(k: i32, e: i32) -> bool {
+    if e >= 0 {
+        k - e < pow5_len(e as u32)
+    } else {
+        // 5^-e <= 2^(e - k)
+        pow5_len(e.unsigned_abs()) <= e - k
+    }
+}
+
+// Are these floor(log10(2^e)), floor(log10(5^e)) and floor(log2(10^e))?
+// They're for checking the multiply-and-shift versions, which the float
+// printers use.
+pub(crate) const fn log10_pow2_ok(e: i32, log: i32) -> bool {
+    pow10_le_pow2(log, e) && !pow10_le_pow2(log + 1, e)
+}
+
+pub(crate) const fn log10_pow5_ok(e: i32, log: i32) -> bool {
+    pow10_le_pow5(log, e) && !pow10_le_pow5(log + 1, e)
+}
+
+pub(crate) const fn log2_pow10_ok(e: i32, log: i32) -> bool {
+    pow2_le_pow10(log, e) && !pow2_le_pow10(log + 1, e)
+}
+
+// The bits of the smallest float >= 10^k, which might be infinity or past it.
+// That's m * 2^e, with e the exponent of 10^k, or e_min if that's less, and
+// m = ceil(10^k / 2^e), which is 5^k shifted (and rounded up) for k >= 0, and
+// 2^(k - e) / 5^-k, rounded up, for k < 0.
+const fn float_pow10_ceil_bits(k: i32, p: u32, e_min: i32) -> u128 {
+    let n = k.unsigned_abs();
+    let pow5 = &POW5S[n as usize];
+    let len = big_bit_len(pow5) as i32;
+    // floor(log2(10^k)), which is k - ceil(log2(5^n)) for k < 0.
+    let log2 = if k >= 0 { k + len - 1 } else { k - len };
+    let mut e = log2 - (p as i32 - 1);
+    if e < e_min {
+        e = e_min;
+    }
+    let m = if k >= 0 && k >= e {
+        // 10^k is an integer multiple of 2^e, and then 5^k fits in m.
+        (pow5[0] << (k - e)) as u128
+    } else if k >= 0 {
+        let (m, sticky) = big_shr(pow5, (e - k) as u32);
+        m as u128 + sticky as u128
+    } else {
+        ceil_pow2_div(k - e, pow5)
+    };
+    (((e - e_min) as u128) << (p - 1)) + m
+}
+
+// ceil(2^t / d), for d = 5^n with n >= 1, when that fits in a u128.
+// d is never a power of two, so the quotient is never exact.
+const fn ceil_pow2_div(t: i32, d: &Big) -> u128 {
+    let len = big_bit_len(d) as i32;
+    if t < len {
+        // 2^t < d, so the quotient is less than 1.
+        return 1;
+    }
+    // Usually the top 64 bits of d are enough: with them as top,
+    // 2^t / d is between 2^s / (top + 1) and 2^s / top, and when those have
+    // the same floor, so does 2^t / d.
+    if len > 64 {
+        let (top, _) = big_shr(d, (len - 64) as u32);
+        let s = t - (len - 64);
+        if s < 128 {
+            let low = (1 << s) / (top as u128 + 1);
+            if low == (1 << s) / top as u128 {
+                return low + 1;
+            }
+        }
+    }
+    // Otherwise it's long division, a bit at a time.  The numerator is a one
+    // and then t zeros, and the first len bits of it make 2^(len - 1), which
+    // is less than d, so the quotient starts after them.
+    let mut rem = big_shl(&big(1), len as u32 - 1);
+    let mut quotient: u128 = 0;
+    let mut bits = t - (len - 1);
+    while bits > 0 {
+        rem = big_shl(&rem, 1);
+        quotient <<= 1;
+        if !big_lt(&rem, d) {
+            rem = big_sub(&rem, d);
+            quotient |= 1;
+        }
+        bits -= 1;
+    }
+    quotient + 1
+}
+
+// Compares the positive float with these bits to 10^k, exactly: 1 if it's
+// bigger, 0 if it's equal, and -1 if it's less.  This multiplies where
+// float_pow10_ceil_bits divides, as a check on it.
+const fn float_cmp_pow10(bits: u128, k: i32, p: u32, e_min: i32) -> i32 {
+    let field = (bits >> (p - 1)) as i32;
+    let (m, e) = if field == 0 {
+        (bits as u64, e_min)
+    } else {
+        ((bits as u64 & ((1 << (p - 1)) - 1)) | 1 << (p - 1), e_min + field - 1)
+    };
+    let pow5 = &POW5S[k.unsigned_abs() as usize];
+    // m * 2^e against 5^k * 2^k, or m * 5^-k * 2^e against 2^k,
+    // with the power of two moved over to whichever side keeps it positive.
+    let (mut left, mut right) = if k >= 0 {
+        (big(m), *pow5)
+    } else {
+        (big_mul(pow5, m), big(1))
+    };
+    if e >= k {
+        left = big_shl(&left, (e - k) as u32);
+    } else {
+        right = big_shl(&right, (k - e) as u32);
+    }
+    if big_lt(&left, &right) {
+        -1
+    } else if big_lt(&right, &left) {
+        1
+    } else {
+        0
+    }
+}
+
+// make_float_limits(p, e_min, max, log_min)[log - log_min] is the bits of the
+// highest float x for which floor(log10(x)) == log, i.e. the bits of the
+// smallest float >= 10^(log + 1), minus one, or max, the bits of the highest
+// finite float, once that's more than max.
+pub(crate) const fn make_float_limits<const N: usize>(
+    p: u32,
+    e_min: i32,
+    max: u128,
+    log_min: i32,
+) -> [u128; N] {
+    let mut limits = [max; N];
+    let mut i = 0;
+    while i < N {
+        let bits = float_pow10_ceil_bits(log_min + i as i32 + 1, p, e_min);
+        if bits - 1 > max {
+            break;
+        }
+        limits[i] = bits - 1;
+        i += 1;
+    }
+    limits
+}
+
+// Does every limit match its definition?  There's one for every guess.
+pub(crate) const fn float_limits_ok(
+    p: u32,
+    e_min: i32,
+    max: u128,
+    log_min: i32,
+    limits: &[u128],
+) -> bool {
+    let mut i = 0;
+    while i < limits.len() {
+        let pow = log_min + i as i32 + 1;
+        let limit = limits[i];
+        if limit == max {
+            // There's no float as big as 10^pow.
+            if float_cmp_pow10(max, pow, p, e_min) >= 0 {
+                return false;
+            }
+        } else if float_cmp_pow10(limit, pow, p, e_min) >= 0
+            || float_cmp_pow10(limit + 1, pow, p, e_min) < 0
+        {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
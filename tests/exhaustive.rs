@@ -0,0 +1,224 @@
+// Exhaustive and differential checks, too slow to run all the time:
+//
+//   cargo test --release --features exhaustive --test exhaustive
+//
+// Every u16 and i16 and every u32 is checked against the repeated-division
+// versions in ilog10::reference and against std's ilog10 (and display_len
+// against to_string), every u32 is checked to strip down to a mantissa that
+// doesn't end in 0, and every implementation, including division by every
+// power of ten, is checked around every power of ten and every power of two
+// for u64 and u128.  Every f32 is checked to step up its log10_floor exactly
+// at the powers of ten, and f64s around every power of ten and two.  The
+// float-printing logarithms are checked over their whole ranges against the
+// reference versions, which work out the big powers.
+#![cfg(feature = "exhaustive")]
+
+use ilog10::reference::{
+    decimal_digits as naive_digits, log10_ceil as naive_ceil, log10_floor as naive,
+    float_log10_floor as naive_float, log10_round as naive_round,
+};
+use ilog10::reference;
+use ilog10::strategy::{BinarySearch, CarryTable, Linear, TwoTable};
+use ilog10::*;
+
+#[test]
+fn all_u16() {
+    for x in 1..=u16::MAX {
+        let expected = naive(x as u128);
+        assert_eq!(log10_floor(x) as u32, expected, "{}", x);
+        assert_eq!(x.log10_floor(), expected, "{}", x);
+        assert_eq!(x.ilog10(), expected, "{}", x);
+        assert_eq!(x.log10_floor_with::<CarryTable>(), expected, "{}", x);
+        assert_eq!(x.log10_floor_with::<BinarySearch>(), expected, "{}", x);
+        assert_eq!(x.log10_floor_with::<Linear>(), expected, "{}", x);
+        assert_eq!(x.log10_ceil(), naive_ceil(x as u128), "{}", x);
+        assert_eq!(x.log10_round(), naive_round(x as u128), "{}", x);
+        assert_eq!(x.display_len() as usize, x.to_string().len(), "{}", x);
+        assert_eq!(x.display_len(), reference::display_len(x as u128, false), "{}", x);
+        assert_eq!(x.is_power_of_ten(), reference::is_power_of_ten(x as u128), "{}", x);
+        assert_eq!(x.prev_power_of_ten() as u128, reference::prev_power_of_ten(x as u128), "{}", x);
+        assert_eq!(
+            x.checked_next_power_of_ten().map(u128::from),
+            reference::next_power_of_ten(x as u128).filter(|&pow| pow <= u16::MAX as u128),
+            "{}",
+            x
+        );
+        for k in 0..=5 {
+            let (quotient, rem) = reference::divrem_pow10(x as u128, k);
+            assert_eq!(x.divrem_pow10(k), (quotient as u16, rem as u16), "{} {}", x, k);
+        }
+        assert_eq!(x.decimal_trailing_zeros(), reference::decimal_trailing_zeros(x as u128), "{}", x);
+    }
+}
+
+#[test]
+fn all_i16() {
+    for x in i16::MIN..=i16::MAX {
+        assert_eq!(x.display_len() as usize, x.to_string().len(), "{}", x);
+        assert_eq!(x.display_len(), reference::display_len(x.unsigned_abs() as u128, x < 0), "{}", x);
+        if x == 0 {
+            continue;
+        }
+        let magnitude = x.unsigned_abs() as u128;
+        let (mantissa, zeros) = reference::strip_decimal_zeros(magnitude);
+        assert_eq!(x.strip_decimal_zeros(), (x.signum() * mantissa as i16, zeros), "{}", x);
+        assert_eq!(x.log10_floor(), naive(magnitude), "{}", x);
+        assert_eq!(x.log10_ceil(), naive_ceil(magnitude), "{}", x);
+        assert_eq!(x.log10_round(), naive_round(magnitude), "{}", x);
+        let (log, pow) = reference::log10_floor_with_pow(magnitude);
+        assert_eq!(x.log10_floor_with_pow(), (log, x.signum() * pow as i16), "{}", x);
+        let (log, rem) = reference::log10_floor_rem(magnitude);
+        assert_eq!(x.log10_floor_rem(), (log, x.signum() * rem as i16), "{}", x);
+    }
+}
+
+// Dividing every u32 down to nothing would take far longer than the functions
+// being tested, so the reference is evaluated at both ends of each power of
+// ten's range, and every x in between is expected to give the same answer.
+#[test]
+fn all_u32() {
+    let mut start = 1u32;
+    loop {
+        let end = start.checked_mul(10).map_or(u32::MAX, |next| next - 1);
+        let expected = naive(start as u128);
+        assert_eq!(naive(end as u128), expected);
+        for x in start..=end {
+            assert_eq!(x.log10_floor(), expected, "{}", x);
+            // That's the mantissa and exponent exactly when the mantissa
+            // doesn't end in 0 itself.
+            let (mantissa, zeros) = x.strip_decimal_zeros();
+            assert!(mantissa % 10 != 0, "{}", x);
+            assert_eq!(mantissa as u64 * 10u64.pow(zeros), x as u64, "{}", x);
+            assert_eq!(log10_u32(x), expected, "{}", x);
+            assert_eq!(x.ilog10(), expected, "{}", x);
+            assert_eq!(x.log10_floor_with::<BinarySearch>(), expected, "{}", x);
+        }
+        if end == u32::MAX {
+            break;
+        }
+        start = end + 1;
+    }
+}
+
+// The values around every power of ten and every power of two up to the width.
+fn edges(bits: u32) -> Vec<u128> {
+    let max = u128::MAX >> (128 - bits);
+    let mut edges = Vec::new();
+    let mut pow = 1u128;
+    while pow <= max {
+        edges.extend([pow - 1, pow, pow + 1]);
+        pow = match pow.checked_mul(10) {
+            Some(next) => next,
+            None => break,
+        };
+    }
+    for n in 0..bits {
+        edges.extend([(1 << n) - 1, 1 << n, (1 << n) + 1]);
+    }
+    edges.push(max - 1);
+    edges.push(max);
+    edges.retain(|&x| x != 0 && x <= max);
+    edges
+}
+
+#[test]
+fn edges_u64() {
+    for x in edges(64) {
+        let expected = naive(x);
+        let x = x as u64;
+        assert_eq!(x.log10_floor(), expected, "{}", x);
+        assert_eq!(log10_u64(x), expected, "{}", x);
+        assert_eq!(x.ilog10(), expected, "{}", x);
+        assert_eq!(x.log10_floor_with::<TwoTable>(), expected, "{}", x);
+        assert_eq!(x.log10_floor_with::<CarryTable>(), expected, "{}", x);
+        assert_eq!(x.log10_floor_with::<BinarySearch>(), expected, "{}", x);
+        assert_eq!(x.log10_floor_with::<Linear>(), expected, "{}", x);
+        assert_eq!(x.log10_ceil(), naive_ceil(x as u128), "{}", x);
+        assert_eq!(x.log10_round(), naive_round(x as u128), "{}", x);
+        assert_eq!(x.decimal_digits(), naive_digits(x as u128), "{}", x);
+        for k in 0..=40 {
+            let (quotient, rem) = reference::divrem_pow10(x as u128, k);
+            assert_eq!(x.divrem_pow10(k), (quotient as u64, rem as u64), "{} {}", x, k);
+        }
+        if x <= i64::MAX as u64 {
+            assert_eq!((-(x as i64)).log10_floor(), expected, "{}", x);
+        }
+    }
+}
+
+#[test]
+fn edges_u128() {
+    for x in edges(128) {
+        let expected = naive(x);
+        assert_eq!(x.log10_floor(), expected, "{}", x);
+        assert_eq!(log10_u128(x), expected, "{}", x);
+        assert_eq!(x.ilog10(), expected, "{}", x);
+        assert_eq!(x.log10_floor_with::<TwoTable>(), expected, "{}", x);
+        assert_eq!(x.log10_floor_with::<CarryTable>(), expected, "{}", x);
+        assert_eq!(x.log10_floor_with::<BinarySearch>(), expected, "{}", x);
+        assert_eq!(x.log10_floor_with::<Linear>(), expected, "{}", x);
+        assert_eq!(x.log10_ceil(), naive_ceil(x), "{}", x);
+        assert_eq!(x.log10_round(), naive_round(x), "{}", x);
+        assert_eq!(x.decimal_digits(), naive_digits(x), "{}", x);
+        for k in 0..=40 {
+            assert_eq!(x.divrem_pow10(k), reference::divrem_pow10(x, k), "{} {}", x, k);
+        }
+        if x <= i128::MAX as u128 {
+            assert_eq!((-(x as i128)).log10_floor(), expected, "{}", x);
+        }
+    }
+}
+
+// Every positive finite f32, in order, which means every bit pattern from 1 up
+// to that of MAX.  log10_floor should go up by one at a time, and only at
+// powers of ten, which is checked against the reference version.
+#[test]
+fn all_f32() {
+    let mut log = f32::MIN_LOG10;
+    assert_eq!(naive_float(f32::from_bits(1) as f64), log);
+    for bits in 1..=f32::MAX.to_bits() {
+        let x = f32::from_bits(bits);
+        let next = x.log10_floor();
+        if next != log {
+            assert_eq!(next, log + 1, "{:e}", x);
+            assert_eq!(naive_float(x as f64), next, "{:e}", x);
+            assert_eq!(naive_float(f32::from_bits(bits - 1) as f64), log, "{:e}", x);
+            log = next;
+        }
+        assert_eq!((-x).log10_floor(), next, "{:e}", x);
+    }
+    assert_eq!(log, f32::MAX_LOG10);
+}
+
+// The f64s around every power of ten and every power of two, subnormals
+// included, against the reference version.
+#[test]
+fn edges_f64() {
+    let mut centers = Vec::new();
+    for k in f64::MIN_LOG10..=f64::MAX_LOG10 + 1 {
+        centers.push(format!("1e{}", k).parse::<f64>().unwrap().to_bits());
+    }
+    for e in -1074..=1023 {
+        centers.push(2f64.powi(e).to_bits());
+    }
+    for center in centers {
+        for bits in center.saturating_sub(4)..=center + 4 {
+            let x = f64::from_bits(bits);
+            if x != 0.0 && x.is_finite() {
+                assert_eq!(x.log10_floor(), naive_float(x), "{:e}", x);
+            }
+        }
+    }
+}
+
+// The float-printing logarithms over their whole ranges.
+#[test]
+fn float_printing() {
+    for e in -2620..=2620 {
+        assert_eq!(floor_log10_pow2(e), reference::floor_log10_pow2(e), "{}", e);
+        assert_eq!(floor_log10_pow5(e), reference::floor_log10_pow5(e), "{}", e);
+    }
+    for e in -1233..=1233 {
+        assert_eq!(floor_log2_pow10(e), reference::floor_log2_pow10(e), "{}", e);
+    }
+}